@@ -0,0 +1,51 @@
+use crate::ValidationError;
+use std::error::Error;
+use std::fmt;
+
+/// The reason why bytes couldn't be decoded into an expression.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecodeError {
+    /// The input doesn't start with the expected magic bytes.
+    InvalidHeader,
+    UnsupportedVersion(u8),
+    /// The input is too short to hold a header and a checksum,
+    /// or shorter than its checksummed header announces.
+    Truncated,
+    /// The input is longer than its checksummed header announces.
+    TrailingBytes,
+    /// The checksum doesn't match, as for input that was corrupted, cut short or extended.
+    ChecksumMismatch,
+    /// The checksum matches but the encoded tokens are malformed.
+    MalformedPayload,
+    InvalidOpcode(u8),
+    /// The predicate encoder rejected the predicate with this index
+    /// (counting only predicates, in token order).
+    InvalidPredicate(usize),
+    InvalidExpression(ValidationError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidHeader => write!(f, "invalid header"),
+            DecodeError::UnsupportedVersion(v) => write!(f, "unsupported format version {v}"),
+            DecodeError::Truncated => write!(f, "truncated input"),
+            DecodeError::TrailingBytes => write!(f, "trailing bytes after the expression"),
+            DecodeError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            DecodeError::MalformedPayload => write!(f, "malformed payload"),
+            DecodeError::InvalidOpcode(opcode) => write!(f, "invalid opcode {opcode}"),
+            DecodeError::InvalidPredicate(i) => write!(f, "invalid predicate {i}"),
+            DecodeError::InvalidExpression(e) => write!(f, "invalid expression: {e}"),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::InvalidExpression(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    InfixExpression, InfixToken, Operator, PostfixExpression, PostfixToken, ValidationError,
+};
+
+/// An expression represented as a tree of operators whose leaves are predicates.
+///
+/// A tree is always well-formed, and converts losslessly to and from
+/// [`PostfixExpression`]; conversions to and from [`InfixExpression`]
+/// preserve the structure but drop redundant parentheses.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExpressionTree<Predicate> {
+    Leaf(Predicate),
+    Not(Box<Self>),
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+    Xor(Box<Self>, Box<Self>),
+    Nand(Box<Self>, Box<Self>),
+    Nor(Box<Self>, Box<Self>),
+    Implies(Box<Self>, Box<Self>),
+    Iff(Box<Self>, Box<Self>),
+}
+
+enum Node<Predicate> {
+    Leaf(Predicate),
+    Unary(Operator, ExpressionTree<Predicate>),
+    Binary(
+        Operator,
+        ExpressionTree<Predicate>,
+        ExpressionTree<Predicate>,
+    ),
+}
+
+enum Visit<Predicate> {
+    Tree(ExpressionTree<Predicate>),
+    Operator(Operator),
+}
+
+impl<Predicate> ExpressionTree<Predicate> {
+    pub fn try_from_infix_tokens(
+        tokens: Vec<InfixToken<Predicate>>,
+    ) -> Result<Self, ValidationError> {
+        InfixExpression::try_from_tokens(tokens).map(Self::from)
+    }
+
+    pub fn try_from_postfix_tokens(
+        tokens: Vec<PostfixToken<Predicate>>,
+    ) -> Result<Self, ValidationError> {
+        PostfixExpression::try_from_tokens(tokens).map(Self::from)
+    }
+
+    /// Returns the outermost operator of the tree, or `None` if the tree is a single leaf.
+    #[must_use]
+    pub fn operator(&self) -> Option<Operator> {
+        match self {
+            ExpressionTree::Leaf(_) => None,
+            ExpressionTree::Not(_) => Some(Operator::Not),
+            ExpressionTree::And(..) => Some(Operator::And),
+            ExpressionTree::Or(..) => Some(Operator::Or),
+            ExpressionTree::Xor(..) => Some(Operator::Xor),
+            ExpressionTree::Nand(..) => Some(Operator::Nand),
+            ExpressionTree::Nor(..) => Some(Operator::Nor),
+            ExpressionTree::Implies(..) => Some(Operator::Implies),
+            ExpressionTree::Iff(..) => Some(Operator::Iff),
+        }
+    }
+
+    #[must_use]
+    pub fn to_postfix(self) -> PostfixExpression<Predicate> {
+        let mut tokens = Vec::new();
+        let mut stack = vec![Visit::Tree(self)];
+
+        while let Some(visit) = stack.pop() {
+            match visit {
+                Visit::Operator(op) => tokens.push(PostfixToken::Operator(op)),
+                Visit::Tree(tree) => match tree.into_node() {
+                    Node::Leaf(p) => tokens.push(PostfixToken::Predicate(p)),
+                    Node::Unary(op, a) => {
+                        stack.push(Visit::Operator(op));
+                        stack.push(Visit::Tree(a));
+                    }
+                    Node::Binary(op, a, b) => {
+                        stack.push(Visit::Operator(op));
+                        stack.push(Visit::Tree(b));
+                        stack.push(Visit::Tree(a));
+                    }
+                },
+            }
+        }
+
+        PostfixExpression::from_tokens_unchecked(tokens)
+    }
+
+    #[must_use]
+    pub fn to_infix(self) -> InfixExpression<Predicate> {
+        self.to_postfix().to_infix()
+    }
+
+    pub(crate) fn from_operator(op: Operator, mut operands: Vec<Self>) -> Self {
+        let b = operands.pop().map(Box::new);
+        let a = operands.pop().map(Box::new);
+        match (op, a, b) {
+            (Operator::Not, None, Some(a)) => ExpressionTree::Not(a),
+            (Operator::And, Some(a), Some(b)) => ExpressionTree::And(a, b),
+            (Operator::Or, Some(a), Some(b)) => ExpressionTree::Or(a, b),
+            (Operator::Xor, Some(a), Some(b)) => ExpressionTree::Xor(a, b),
+            (Operator::Nand, Some(a), Some(b)) => ExpressionTree::Nand(a, b),
+            (Operator::Nor, Some(a), Some(b)) => ExpressionTree::Nor(a, b),
+            (Operator::Implies, Some(a), Some(b)) => ExpressionTree::Implies(a, b),
+            (Operator::Iff, Some(a), Some(b)) => ExpressionTree::Iff(a, b),
+            _ => unreachable!("wrong number of operands for {op:?}"),
+        }
+    }
+
+    fn into_node(self) -> Node<Predicate> {
+        match self {
+            ExpressionTree::Leaf(p) => Node::Leaf(p),
+            ExpressionTree::Not(a) => Node::Unary(Operator::Not, *a),
+            ExpressionTree::And(a, b) => Node::Binary(Operator::And, *a, *b),
+            ExpressionTree::Or(a, b) => Node::Binary(Operator::Or, *a, *b),
+            ExpressionTree::Xor(a, b) => Node::Binary(Operator::Xor, *a, *b),
+            ExpressionTree::Nand(a, b) => Node::Binary(Operator::Nand, *a, *b),
+            ExpressionTree::Nor(a, b) => Node::Binary(Operator::Nor, *a, *b),
+            ExpressionTree::Implies(a, b) => Node::Binary(Operator::Implies, *a, *b),
+            ExpressionTree::Iff(a, b) => Node::Binary(Operator::Iff, *a, *b),
+        }
+    }
+}
+
+impl<Predicate> From<PostfixExpression<Predicate>> for ExpressionTree<Predicate> {
+    fn from(postfix: PostfixExpression<Predicate>) -> Self {
+        postfix.to_tree()
+    }
+}
+
+impl<Predicate> From<InfixExpression<Predicate>> for ExpressionTree<Predicate> {
+    fn from(infix: InfixExpression<Predicate>) -> Self {
+        infix.to_tree()
+    }
+}
+
+impl<Predicate> From<ExpressionTree<Predicate>> for PostfixExpression<Predicate> {
+    fn from(tree: ExpressionTree<Predicate>) -> Self {
+        tree.to_postfix()
+    }
+}
+
+impl<Predicate> From<ExpressionTree<Predicate>> for InfixExpression<Predicate> {
+    fn from(tree: ExpressionTree<Predicate>) -> Self {
+        tree.to_infix()
+    }
+}
@@ -0,0 +1,33 @@
+/// A family of fuzzy conjunction (t-norm) and disjunction (t-conorm) operators, used to
+/// combine degrees of truth in [0, 1].
+///
+/// All families agree with boolean logic on 0 and 1. Negation is always `1 - a`.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FuzzyLogic {
+    /// Gödel (Zadeh) logic: `min(a, b)` and `max(a, b)`
+    #[default]
+    MinMax,
+    /// `a * b` and the probabilistic sum `a + b - a * b`
+    Product,
+    /// Łukasiewicz logic: `max(0, a + b - 1)` and `min(1, a + b)`
+    Lukasiewicz,
+}
+
+impl FuzzyLogic {
+    pub(crate) fn t_norm(self, a: f64, b: f64) -> f64 {
+        match self {
+            FuzzyLogic::MinMax => a.min(b),
+            FuzzyLogic::Product => a * b,
+            FuzzyLogic::Lukasiewicz => (a + b - 1.0).max(0.0),
+        }
+    }
+
+    pub(crate) fn t_conorm(self, a: f64, b: f64) -> f64 {
+        match self {
+            FuzzyLogic::MinMax => a.max(b),
+            FuzzyLogic::Product => a + b - a * b,
+            FuzzyLogic::Lukasiewicz => (a + b).min(1.0),
+        }
+    }
+}
@@ -1,9 +1,42 @@
 use crate::enums::operator::Operator;
 use crate::Parenthesis;
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InfixToken<Predicate> {
     Parenthesis(Parenthesis),
     Operator(Operator),
     Predicate(Predicate),
 }
+
+impl<Predicate> InfixToken<Predicate> {
+    pub(crate) fn as_ref(&self) -> InfixToken<&Predicate> {
+        match self {
+            InfixToken::Parenthesis(parenthesis) => InfixToken::Parenthesis(*parenthesis),
+            InfixToken::Operator(op) => InfixToken::Operator(*op),
+            InfixToken::Predicate(p) => InfixToken::Predicate(p),
+        }
+    }
+
+    pub(crate) fn try_map<Q, E>(
+        self,
+        f: impl FnOnce(Predicate) -> Result<Q, E>,
+    ) -> Result<InfixToken<Q>, E> {
+        match self {
+            InfixToken::Parenthesis(parenthesis) => Ok(InfixToken::Parenthesis(parenthesis)),
+            InfixToken::Operator(op) => Ok(InfixToken::Operator(op)),
+            InfixToken::Predicate(p) => f(p).map(InfixToken::Predicate),
+        }
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for InfixToken<Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InfixToken::Parenthesis(parenthesis) => parenthesis.fmt(f),
+            InfixToken::Operator(op) => op.fmt(f),
+            InfixToken::Predicate(p) => p.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// The set of keywords used to render operators as text.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeywordStyle {
+    /// `AND`, `OR`, `NOT`, `XOR`, `NAND`, `NOR`, `IMPLIES`, `IFF`
+    #[default]
+    Words,
+    /// `&&`, `||`, `!`, `^`, `!&`, `!|`, `->`, `<->`
+    Symbols,
+    /// `∧`, `∨`, `¬`, `⊕`, `↑`, `↓`, `→`, `↔`
+    Logic,
+}
+
+impl KeywordStyle {
+    pub(crate) const ALL: [KeywordStyle; 3] = [
+        KeywordStyle::Words,
+        KeywordStyle::Symbols,
+        KeywordStyle::Logic,
+    ];
+}
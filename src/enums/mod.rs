@@ -1,4 +1,15 @@
+pub mod decode_error;
+pub mod expression_tree;
+pub mod fuzzy_logic;
 pub mod infix_token;
+pub mod keyword_style;
 pub mod operator;
 pub mod parenthesis;
+pub mod parse_error;
+pub mod partial_evaluation;
 pub mod postfix_token;
+pub mod prefix_token;
+pub mod table_style;
+pub mod trace_step;
+pub mod truth;
+pub mod validation_error;
@@ -1,16 +1,204 @@
+use crate::KeywordStyle;
+use std::cmp::Ordering;
+use std::fmt;
+
 #[derive(PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
     And,
     Or,
+    Not,
+    Xor,
+    Nand,
+    Nor,
+    Implies,
+    Iff,
 }
 
 impl Operator {
+    pub(crate) const ALL: [Operator; 8] = [
+        Operator::And,
+        Operator::Or,
+        Operator::Not,
+        Operator::Xor,
+        Operator::Nand,
+        Operator::Nor,
+        Operator::Implies,
+        Operator::Iff,
+    ];
+
     pub(crate) fn precedence(self) -> u8 {
         match self {
-            Operator::And => 2,
-            Operator::Or => 1,
+            Operator::Not => 6,
+            Operator::And | Operator::Nand => 5,
+            Operator::Xor => 4,
+            Operator::Or | Operator::Nor => 3,
+            Operator::Implies => 2,
+            Operator::Iff => 1,
+        }
+    }
+
+    pub(crate) fn is_right_associative(self) -> bool {
+        matches!(self, Operator::Not | Operator::Implies)
+    }
+
+    /// Whether `(a op b) op c` is equivalent to `a op (b op c)`.
+    pub(crate) fn is_associative(self) -> bool {
+        matches!(
+            self,
+            Operator::And | Operator::Or | Operator::Xor | Operator::Iff
+        )
+    }
+
+    /// Whether an operand whose outermost operator is `operand` must be enclosed in parentheses
+    /// when written as the left or right operand of `self` in infix notation, provided that no
+    /// other operator of the same precedence is written outside parentheses in that operand.
+    pub(crate) fn needs_parenthesis(self, operand: Operator, is_right_operand: bool) -> bool {
+        match operand.precedence().cmp(&self.precedence()) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal if is_right_operand => {
+                !(self.is_right_associative() || (operand == self && self.is_associative()))
+            }
+            Ordering::Equal => self.is_right_associative(),
         }
     }
+
+    pub(crate) fn arity(self) -> usize {
+        match self {
+            Operator::Not => 1,
+            Operator::And
+            | Operator::Or
+            | Operator::Xor
+            | Operator::Nand
+            | Operator::Nor
+            | Operator::Implies
+            | Operator::Iff => 2,
+        }
+    }
+
+    pub(crate) fn is_unary(self) -> bool {
+        self.arity() == 1
+    }
+
+    pub(crate) fn apply(self, operands: &[bool]) -> bool {
+        match (self, operands) {
+            (Operator::Not, [a]) => !a,
+            (Operator::And, [a, b]) => *a && *b,
+            (Operator::Or, [a, b]) => *a || *b,
+            (Operator::Xor, [a, b]) => a != b,
+            (Operator::Nand, [a, b]) => !(*a && *b),
+            (Operator::Nor, [a, b]) => !(*a || *b),
+            (Operator::Implies, [a, b]) => !a || *b,
+            (Operator::Iff, [a, b]) => a == b,
+            _ => unreachable!("wrong number of operands for {self:?}"),
+        }
+    }
+
+    /// Applies the operator bitwise, on words holding the operands of 64 evaluations.
+    pub(crate) fn apply_words(self, operands: &[u64]) -> u64 {
+        match (self, operands) {
+            (Operator::Not, [a]) => !a,
+            (Operator::And, [a, b]) => a & b,
+            (Operator::Or, [a, b]) => a | b,
+            (Operator::Xor, [a, b]) => a ^ b,
+            (Operator::Nand, [a, b]) => !(a & b),
+            (Operator::Nor, [a, b]) => !(a | b),
+            (Operator::Implies, [a, b]) => !a | b,
+            (Operator::Iff, [a, b]) => !(a ^ b),
+            _ => unreachable!("wrong number of operands for {self:?}"),
+        }
+    }
+
+    /// Whether the operand at `position` determines the result on its own when it has `value`.
+    pub(crate) fn is_controlling(self, position: usize, value: bool) -> bool {
+        matches!(
+            (self, position, value),
+            (Operator::Not, _, _)
+                | (Operator::And | Operator::Nand, _, false)
+                | (Operator::Or | Operator::Nor, _, true)
+                | (Operator::Implies, 0, false)
+                | (Operator::Implies, 1, true)
+        )
+    }
+
+    /// Returns the result of the operator if it's already determined by its first operand.
+    pub(crate) fn short_circuit(self, first_operand: bool) -> Option<bool> {
+        match (self, first_operand) {
+            (Operator::And, false) | (Operator::Nor, true) => Some(false),
+            (Operator::Or, true) | (Operator::Nand | Operator::Implies, false) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Returns the keyword used to represent this operator in the given style.
+    #[must_use]
+    pub fn keyword(self, style: KeywordStyle) -> &'static str {
+        match style {
+            KeywordStyle::Words => match self {
+                Operator::And => "AND",
+                Operator::Or => "OR",
+                Operator::Not => "NOT",
+                Operator::Xor => "XOR",
+                Operator::Nand => "NAND",
+                Operator::Nor => "NOR",
+                Operator::Implies => "IMPLIES",
+                Operator::Iff => "IFF",
+            },
+            KeywordStyle::Symbols => match self {
+                Operator::And => "&&",
+                Operator::Or => "||",
+                Operator::Not => "!",
+                Operator::Xor => "^",
+                Operator::Nand => "!&",
+                Operator::Nor => "!|",
+                Operator::Implies => "->",
+                Operator::Iff => "<->",
+            },
+            KeywordStyle::Logic => match self {
+                Operator::And => "∧",
+                Operator::Or => "∨",
+                Operator::Not => "¬",
+                Operator::Xor => "⊕",
+                Operator::Nand => "↑",
+                Operator::Nor => "↓",
+                Operator::Implies => "→",
+                Operator::Iff => "↔",
+            },
+        }
+    }
+
+    pub(crate) fn from_keyword(keyword: &str) -> Option<Self> {
+        KeywordStyle::ALL.into_iter().find_map(|style| {
+            Operator::ALL
+                .into_iter()
+                .find(|op| op.keyword(style).eq_ignore_ascii_case(keyword))
+        })
+    }
+
+    /// Returns the byte representing this operator in the binary encoding.
+    pub(crate) fn opcode(self) -> u8 {
+        match self {
+            Operator::And => 1,
+            Operator::Or => 2,
+            Operator::Not => 3,
+            Operator::Xor => 4,
+            Operator::Nand => 5,
+            Operator::Nor => 6,
+            Operator::Implies => 7,
+            Operator::Iff => 8,
+        }
+    }
+
+    pub(crate) fn from_opcode(opcode: u8) -> Option<Self> {
+        Operator::ALL.into_iter().find(|op| op.opcode() == opcode)
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.keyword(KeywordStyle::default()))
+    }
 }
 
 #[cfg(test)]
@@ -19,6 +207,142 @@ mod tests {
 
     #[test]
     fn test_operators_precedence() {
+        assert!(Operator::Not.precedence() > Operator::And.precedence());
         assert!(Operator::And.precedence() > Operator::Or.precedence());
+        assert_eq!(Operator::And.precedence(), Operator::Nand.precedence());
+        assert!(Operator::And.precedence() > Operator::Xor.precedence());
+        assert!(Operator::Xor.precedence() > Operator::Or.precedence());
+        assert_eq!(Operator::Or.precedence(), Operator::Nor.precedence());
+        assert!(Operator::Or.precedence() > Operator::Implies.precedence());
+        assert!(Operator::Implies.precedence() > Operator::Iff.precedence());
+    }
+
+    #[test]
+    fn test_operators_opcode() {
+        for op in Operator::ALL {
+            assert_ne!(op.opcode(), 0);
+            assert_eq!(Operator::from_opcode(op.opcode()), Some(op));
+        }
+        assert_eq!(Operator::from_opcode(0), None);
+        assert_eq!(Operator::from_opcode(9), None);
+    }
+
+    #[test]
+    fn test_operators_apply_words() {
+        for op in Operator::ALL {
+            for bits in 0..4u64 {
+                let operands = [bits & 1 != 0, bits & 2 != 0];
+                let operands = &operands[..op.arity()];
+                let words: Vec<u64> = operands.iter().map(|&b| if b { !0 } else { 0 }).collect();
+                let expected = if op.apply(operands) { !0 } else { 0 };
+                assert_eq!(op.apply_words(&words), expected, "{op:?} {operands:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_operators_is_controlling() {
+        for op in Operator::ALL {
+            for position in 0..op.arity() {
+                for value in [false, true] {
+                    let mut results = [false, true].into_iter().map(|other| {
+                        let mut operands = [other; 2];
+                        operands[position] = value;
+                        op.apply(&operands[..op.arity()])
+                    });
+                    let first = results.next();
+                    let determined = results.all(|r| Some(r) == first);
+                    assert_eq!(op.is_controlling(position, value), determined, "{op:?}");
+                }
+            }
+            for first in [false, true] {
+                assert_eq!(
+                    op.short_circuit(first).is_some(),
+                    !op.is_unary() && op.is_controlling(0, first)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_operators_associativity() {
+        for op in Operator::ALL {
+            assert_eq!(
+                op.is_right_associative(),
+                matches!(op, Operator::Not | Operator::Implies)
+            );
+        }
+    }
+
+    #[test]
+    fn test_operators_needs_parenthesis() {
+        // a*(b+c)
+        assert!(Operator::And.needs_parenthesis(Operator::Or, true));
+        // a+b*c
+        assert!(!Operator::Or.needs_parenthesis(Operator::And, true));
+        // a+b+c
+        assert!(!Operator::Or.needs_parenthesis(Operator::Or, false));
+        assert!(!Operator::Or.needs_parenthesis(Operator::Or, true));
+        // a nand (b nand c)
+        assert!(!Operator::Nand.needs_parenthesis(Operator::Nand, false));
+        assert!(Operator::Nand.needs_parenthesis(Operator::Nand, true));
+        // a and (b nand c)
+        assert!(Operator::And.needs_parenthesis(Operator::Nand, true));
+        // (a -> b) -> c
+        assert!(Operator::Implies.needs_parenthesis(Operator::Implies, false));
+        assert!(!Operator::Implies.needs_parenthesis(Operator::Implies, true));
+        // !!a
+        assert!(!Operator::Not.needs_parenthesis(Operator::Not, true));
+        assert!(Operator::Not.needs_parenthesis(Operator::And, true));
+    }
+
+    #[test]
+    fn test_operators_short_circuit() {
+        for op in Operator::ALL.into_iter().filter(|op| !op.is_unary()) {
+            for a in [false, true] {
+                if let Some(result) = op.short_circuit(a) {
+                    assert_eq!(op.apply(&[a, false]), result);
+                    assert_eq!(op.apply(&[a, true]), result);
+                } else {
+                    assert_ne!(op.apply(&[a, false]), op.apply(&[a, true]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_operators_arity() {
+        assert_eq!(Operator::Not.arity(), 1);
+        assert_eq!(Operator::And.arity(), 2);
+        assert_eq!(Operator::Or.arity(), 2);
+        assert_eq!(Operator::Implies.arity(), 2);
+    }
+
+    #[test]
+    fn test_operators_keywords_are_unique() {
+        let mut keywords = Vec::new();
+        for style in KeywordStyle::ALL {
+            for op in Operator::ALL {
+                let keyword = op.keyword(style).to_ascii_uppercase();
+                assert!(!keywords.contains(&keyword));
+                keywords.push(keyword);
+            }
+        }
+    }
+
+    #[test]
+    fn test_operators_from_keyword() {
+        assert_eq!(Operator::from_keyword("AND"), Some(Operator::And));
+        assert_eq!(Operator::from_keyword("and"), Some(Operator::And));
+        assert_eq!(Operator::from_keyword("Or"), Some(Operator::Or));
+        assert_eq!(Operator::from_keyword("not"), Some(Operator::Not));
+        assert_eq!(Operator::from_keyword("Implies"), Some(Operator::Implies));
+        assert_eq!(Operator::from_keyword("NAND"), Some(Operator::Nand));
+        assert_eq!(Operator::from_keyword("&&"), Some(Operator::And));
+        assert_eq!(Operator::from_keyword("<->"), Some(Operator::Iff));
+        assert_eq!(Operator::from_keyword("¬"), Some(Operator::Not));
+        assert_eq!(Operator::from_keyword("→"), Some(Operator::Implies));
+        assert_eq!(Operator::from_keyword("ANDOR"), None);
+        assert_eq!(Operator::from_keyword(""), None);
     }
 }
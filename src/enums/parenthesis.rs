@@ -1,5 +1,17 @@
-#[derive(Debug, PartialEq)]
+use std::fmt;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Parenthesis {
     Open,
     Close,
 }
+
+impl fmt::Display for Parenthesis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Parenthesis::Open => f.write_str("("),
+            Parenthesis::Close => f.write_str(")"),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::ValidationError;
+use std::error::Error;
+use std::fmt;
+
+/// The reason why text couldn't be parsed into an expression.
+///
+/// Errors found in the text carry the byte offset in the input where the problem was detected.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError {
+    /// The double quote at this offset is never closed.
+    UnterminatedQuote(usize),
+    /// The backslash at this offset doesn't escape `"` or `\`.
+    InvalidEscape(usize),
+    /// A double quote inside an unquoted word, or a character glued to a closing quote.
+    UnexpectedCharacter(usize),
+    /// A token that doesn't fit the structure of the expression,
+    /// such as an unknown operator or an operator with the wrong number of operands.
+    UnexpectedToken(usize),
+    /// The input ends in the middle of the expression.
+    UnexpectedEnd,
+    /// The predicate parser rejected the predicate with this index
+    /// (counting only predicates, in token order).
+    InvalidPredicate(usize),
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnterminatedQuote(i) => write!(f, "unterminated quote at byte {i}"),
+            ParseError::InvalidEscape(i) => write!(f, "invalid escape at byte {i}"),
+            ParseError::UnexpectedCharacter(i) => write!(f, "unexpected character at byte {i}"),
+            ParseError::UnexpectedToken(i) => write!(f, "unexpected token at byte {i}"),
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseError::InvalidPredicate(i) => write!(f, "invalid predicate {i}"),
+            ParseError::Invalid(e) => write!(f, "invalid expression: {e}"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
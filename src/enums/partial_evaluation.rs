@@ -0,0 +1,12 @@
+use crate::PostfixExpression;
+
+/// The outcome of
+/// [`PostfixExpression::partially_evaluate`](crate::PostfixExpression::partially_evaluate).
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartialEvaluation<Predicate> {
+    /// The known predicates were enough to determine the value of the expression.
+    Value(bool),
+    /// The expression left to evaluate once the unknown predicates are known.
+    Residual(PostfixExpression<Predicate>),
+}
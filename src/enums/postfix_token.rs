@@ -1,7 +1,37 @@
 use crate::enums::operator::Operator;
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PostfixToken<Predicate> {
     Operator(Operator),
     Predicate(Predicate),
 }
+
+impl<Predicate> PostfixToken<Predicate> {
+    pub(crate) fn as_ref(&self) -> PostfixToken<&Predicate> {
+        match self {
+            PostfixToken::Operator(op) => PostfixToken::Operator(*op),
+            PostfixToken::Predicate(p) => PostfixToken::Predicate(p),
+        }
+    }
+
+    pub(crate) fn try_map<Q, E>(
+        self,
+        f: impl FnOnce(Predicate) -> Result<Q, E>,
+    ) -> Result<PostfixToken<Q>, E> {
+        match self {
+            PostfixToken::Operator(op) => Ok(PostfixToken::Operator(op)),
+            PostfixToken::Predicate(p) => f(p).map(PostfixToken::Predicate),
+        }
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for PostfixToken<Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PostfixToken::Operator(op) => op.fmt(f),
+            PostfixToken::Predicate(p) => p.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::enums::operator::Operator;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrefixToken<Predicate> {
+    Operator(Operator),
+    Predicate(Predicate),
+}
+
+impl<Predicate> PrefixToken<Predicate> {
+    pub(crate) fn as_ref(&self) -> PrefixToken<&Predicate> {
+        match self {
+            PrefixToken::Operator(op) => PrefixToken::Operator(*op),
+            PrefixToken::Predicate(p) => PrefixToken::Predicate(p),
+        }
+    }
+
+    pub(crate) fn try_map<Q, E>(
+        self,
+        f: impl FnOnce(Predicate) -> Result<Q, E>,
+    ) -> Result<PrefixToken<Q>, E> {
+        match self {
+            PrefixToken::Operator(op) => Ok(PrefixToken::Operator(op)),
+            PrefixToken::Predicate(p) => f(p).map(PrefixToken::Predicate),
+        }
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for PrefixToken<Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefixToken::Operator(op) => op.fmt(f),
+            PrefixToken::Predicate(p) => p.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+/// The format used to render a table as text.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableStyle {
+    /// A GitHub-flavored Markdown table
+    #[default]
+    Markdown,
+    /// A plain text table framed with `+`, `-` and `|`
+    Ascii,
+}
@@ -0,0 +1,38 @@
+use crate::Operator;
+
+/// A step of the evaluation of a postfix expression, as recorded by
+/// [`PostfixExpression::evaluate_traced`](crate::PostfixExpression::evaluate_traced).
+///
+/// `index` is the index of the token of the predicate or operator in the expression.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TraceStep {
+    Predicate {
+        index: usize,
+        value: bool,
+    },
+    /// An operator application, whose operands only include the first one when
+    /// it was enough to determine the value.
+    Operator {
+        index: usize,
+        operator: Operator,
+        operands: Vec<bool>,
+        value: bool,
+    },
+}
+
+impl TraceStep {
+    #[must_use]
+    pub fn index(&self) -> usize {
+        match self {
+            TraceStep::Predicate { index, .. } | TraceStep::Operator { index, .. } => *index,
+        }
+    }
+
+    #[must_use]
+    pub fn value(&self) -> bool {
+        match self {
+            TraceStep::Predicate { value, .. } | TraceStep::Operator { value, .. } => *value,
+        }
+    }
+}
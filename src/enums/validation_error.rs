@@ -0,0 +1,50 @@
+use std::fmt;
+
+/// The reason why a sequence of tokens doesn't form a valid expression.
+///
+/// Each variant except [`ValidationError::EmptyExpression`] carries the index of the token
+/// where the problem was detected; problems detected only after the last token
+/// refer to the index one past the end of the sequence.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationError {
+    EmptyExpression,
+    UnbalancedParenthesis(usize),
+    MissingOperand(usize),
+    MissingOperator(usize),
+    AdjacentPredicates(usize),
+    TrailingOperator(usize),
+}
+
+impl ValidationError {
+    #[must_use]
+    pub fn index(self) -> Option<usize> {
+        match self {
+            ValidationError::EmptyExpression => None,
+            ValidationError::UnbalancedParenthesis(i)
+            | ValidationError::MissingOperand(i)
+            | ValidationError::MissingOperator(i)
+            | ValidationError::AdjacentPredicates(i)
+            | ValidationError::TrailingOperator(i) => Some(i),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::EmptyExpression => write!(f, "empty expression"),
+            ValidationError::UnbalancedParenthesis(i) => {
+                write!(f, "unbalanced parenthesis at token {i}")
+            }
+            ValidationError::MissingOperand(i) => write!(f, "missing operand at token {i}"),
+            ValidationError::MissingOperator(i) => write!(f, "missing operator at token {i}"),
+            ValidationError::AdjacentPredicates(i) => {
+                write!(f, "adjacent predicates at token {i}")
+            }
+            ValidationError::TrailingOperator(i) => write!(f, "trailing operator at token {i}"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
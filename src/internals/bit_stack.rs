@@ -0,0 +1,47 @@
+/// A stack of booleans packed as bits, which only allocates beyond 128 entries.
+#[derive(Default)]
+pub(crate) struct BitStack {
+    top: u128,
+    len: usize,
+    spilled: Vec<u128>,
+}
+
+impl BitStack {
+    pub(crate) fn push(&mut self, value: bool) {
+        if self.len > 0 && self.len.is_multiple_of(128) {
+            self.spilled.push(self.top);
+            self.top = 0;
+        }
+        self.top = self.top << 1 | u128::from(value);
+        self.len += 1;
+    }
+
+    pub(crate) fn pop(&mut self) -> bool {
+        let value = self.top & 1 == 1;
+        self.top >>= 1;
+        self.len -= 1;
+        if self.len > 0 && self.len.is_multiple_of(128) {
+            self.top = self.spilled.pop().unwrap_or_default();
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_stack() {
+        let values: Vec<bool> = (0..300).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+        let mut stack = BitStack::default();
+        for &value in &values {
+            stack.push(value);
+        }
+        assert_eq!(stack.spilled.len(), 2);
+        for &value in values.iter().rev() {
+            assert_eq!(stack.pop(), value);
+        }
+        assert!(stack.spilled.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! The binary encoding of postfix expressions.
+//!
+//! An encoded expression is made of:
+//! - the magic bytes `RPNE` and a format version byte,
+//! - the length of the payload, as a LEB128 varint,
+//! - the payload: one opcode per token, where predicates use opcode 0 followed by
+//!   the varint length of their encoding and the encoding itself,
+//! - a little-endian CRC-32 of everything before it.
+
+use crate::{DecodeError, Operator, PostfixToken, PredicateEncoder};
+
+const MAGIC: &[u8; 4] = b"RPNE";
+const VERSION: u8 = 1;
+const PREDICATE_OPCODE: u8 = 0;
+const CHECKSUM_LEN: usize = 4;
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+pub(crate) fn encode_tokens<P>(
+    tokens: &[PostfixToken<P>],
+    encoder: &dyn PredicateEncoder<Predicate = P>,
+) -> Vec<u8> {
+    let mut payload = Vec::new();
+    let mut predicate = Vec::new();
+    for token in tokens {
+        match token {
+            PostfixToken::Operator(op) => payload.push(op.opcode()),
+            PostfixToken::Predicate(p) => {
+                predicate.clear();
+                encoder.encode_predicate(p, &mut predicate);
+                payload.push(PREDICATE_OPCODE);
+                write_varint(&mut payload, predicate.len());
+                payload.extend_from_slice(&predicate);
+            }
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(payload.len() + 16);
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    write_varint(&mut bytes, payload.len());
+    bytes.extend_from_slice(&payload);
+    bytes.extend_from_slice(&crc32(&bytes).to_le_bytes());
+    bytes
+}
+
+/// Decodes the tokens of an expression, without validating them.
+pub(crate) fn decode_tokens<P>(
+    bytes: &[u8],
+    encoder: &dyn PredicateEncoder<Predicate = P>,
+) -> Result<Vec<PostfixToken<P>>, DecodeError> {
+    let mut reader = Reader { bytes };
+    let magic = reader
+        .read_bytes(MAGIC.len())
+        .ok_or(DecodeError::Truncated)?;
+    if magic != MAGIC {
+        return Err(DecodeError::InvalidHeader);
+    }
+    let version = reader.read_u8().ok_or(DecodeError::Truncated)?;
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+
+    // the payload length can't be trusted before the checksum is verified
+    let (content, checksum) = reader
+        .bytes
+        .split_last_chunk::<CHECKSUM_LEN>()
+        .ok_or(DecodeError::Truncated)?;
+    if crc32(&bytes[..bytes.len() - CHECKSUM_LEN]) != u32::from_le_bytes(*checksum) {
+        return Err(DecodeError::ChecksumMismatch);
+    }
+
+    let mut payload = Reader { bytes: content };
+    let payload_len = payload.read_varint().ok_or(DecodeError::Truncated)?;
+    if payload.bytes.len() < payload_len {
+        return Err(DecodeError::Truncated);
+    }
+    if payload.bytes.len() > payload_len {
+        return Err(DecodeError::TrailingBytes);
+    }
+
+    let mut tokens = Vec::new();
+    let mut predicate_index = 0;
+    while let Some(opcode) = payload.read_u8() {
+        let token = if opcode == PREDICATE_OPCODE {
+            let predicate = payload
+                .read_varint()
+                .and_then(|len| payload.read_bytes(len))
+                .ok_or(DecodeError::MalformedPayload)?;
+            let predicate = encoder
+                .decode_predicate(predicate)
+                .ok_or(DecodeError::InvalidPredicate(predicate_index))?;
+            predicate_index += 1;
+            PostfixToken::Predicate(predicate)
+        } else {
+            let op = Operator::from_opcode(opcode).ok_or(DecodeError::InvalidOpcode(opcode))?;
+            PostfixToken::Operator(op)
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// A cursor over the bytes being decoded.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_u8(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        Some(byte)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some(bytes)
+    }
+
+    /// Reads a LEB128 varint, returning `None` if it's cut short or overflows.
+    fn read_varint(&mut self) -> Option<usize> {
+        let mut value: usize = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let bits = usize::from(byte & 0x7F);
+            if shift >= usize::BITS || (bits << shift) >> shift != bits {
+                return None;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+            shift += 7;
+        }
+    }
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        buffer.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xEDB8_8320
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_varint() {
+        for value in [0, 1, 0x7F, 0x80, 300, 0x3FFF, 0x4000, usize::MAX] {
+            let mut buffer = Vec::new();
+            write_varint(&mut buffer, value);
+            let mut reader = Reader { bytes: &buffer };
+            assert_eq!(reader.read_varint(), Some(value));
+            assert!(reader.bytes.is_empty());
+        }
+        assert_eq!(Reader { bytes: &[0x80] }.read_varint(), None);
+        assert_eq!(Reader { bytes: &[0xFF; 11] }.read_varint(), None);
+    }
+}
@@ -0,0 +1,19 @@
+use std::fmt;
+
+pub(crate) struct DisplayWith<F>(F);
+
+impl<F> fmt::Display for DisplayWith<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+pub(crate) fn display_with<F>(fmt: F) -> DisplayWith<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    DisplayWith(fmt)
+}
@@ -0,0 +1,35 @@
+use crate::{InfixToken, KeywordStyle, Parenthesis};
+use std::fmt;
+
+pub(crate) fn write_infix<'a, Predicate: 'a>(
+    f: &mut fmt::Formatter<'_>,
+    tokens: impl IntoIterator<Item = &'a InfixToken<Predicate>>,
+    style: KeywordStyle,
+    mut write_predicate: impl FnMut(&mut fmt::Formatter<'_>, &Predicate) -> fmt::Result,
+) -> fmt::Result {
+    let mut separator_needed = false;
+
+    for token in tokens {
+        if separator_needed && !matches!(token, InfixToken::Parenthesis(Parenthesis::Close)) {
+            f.write_str(" ")?;
+        }
+        match token {
+            InfixToken::Predicate(p) => {
+                write_predicate(f, p)?;
+                separator_needed = true;
+            }
+            InfixToken::Operator(op) => {
+                // symbolic unary operators are separated from their operand too, as `!a`
+                // would be read back as a single predicate
+                f.write_str(op.keyword(style))?;
+                separator_needed = true;
+            }
+            InfixToken::Parenthesis(parenthesis) => {
+                write!(f, "{parenthesis}")?;
+                separator_needed = *parenthesis == Parenthesis::Close;
+            }
+        }
+    }
+
+    Ok(())
+}
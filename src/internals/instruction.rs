@@ -0,0 +1,18 @@
+/// An instruction of a [`CompiledExpression`](crate::CompiledExpression).
+///
+/// Instructions work on a single boolean register, and jumps skip the given number
+/// of instructions when the register holds the expected value.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) enum Instruction {
+    /// Evaluates the predicate with the given index into the register.
+    Load(usize),
+    Not,
+    JumpIfFalse(usize),
+    JumpIfTrue(usize),
+    /// Saves the register on the stack.
+    Push,
+    /// Pops a value from the stack and XORs it into the register.
+    Xor,
+    /// Pops a value from the stack and replaces the register with whether they're equal.
+    Iff,
+}
@@ -0,0 +1,9 @@
+use std::convert::Infallible;
+
+/// Unwraps a result that can't be an error.
+pub(crate) fn into_ok<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
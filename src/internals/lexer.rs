@@ -0,0 +1,291 @@
+use crate::{Operator, Parenthesis, ParseError};
+use std::borrow::Cow;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Lexeme<'a> {
+    Parenthesis(Parenthesis),
+    Word(&'a str),
+}
+
+/// A whitespace-separated word, optionally written between double quotes.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Word<'a> {
+    Plain(&'a str),
+    Quoted(String),
+}
+
+/// A lexeme of an S-expression: a parenthesis or a word, optionally written between double quotes.
+#[derive(Debug, PartialEq)]
+pub(crate) enum SexprLexeme<'a> {
+    Parenthesis(Parenthesis),
+    Word(Word<'a>),
+}
+
+pub(crate) fn lex(input: &str) -> Vec<Lexeme<'_>> {
+    let mut lexemes = Vec::new();
+    let mut word_start: Option<usize> = None;
+
+    for (i, c) in input.char_indices() {
+        let parenthesis = match c {
+            '(' => Some(Parenthesis::Open),
+            ')' => Some(Parenthesis::Close),
+            _ => None,
+        };
+        if parenthesis.is_none() && !c.is_whitespace() {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            lexemes.push(Lexeme::Word(&input[start..i]));
+        }
+        if let Some(parenthesis) = parenthesis {
+            lexemes.push(Lexeme::Parenthesis(parenthesis));
+        }
+    }
+
+    if let Some(start) = word_start {
+        lexemes.push(Lexeme::Word(&input[start..]));
+    }
+
+    lexemes
+}
+
+/// Splits the input into whitespace-separated words.
+///
+/// Words can be enclosed in double quotes to include whitespace, and inside quotes `\"` and `\\`
+/// stand for a double quote and a backslash.
+/// Fails on unterminated quotes, unknown escapes, or quotes within plain words.
+pub(crate) fn lex_words(input: &str) -> Result<Vec<Word<'_>>, ParseError> {
+    let mut words = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            let word = read_quoted(&mut chars)?;
+            if let Some(&(i, c)) = chars.peek() {
+                if !c.is_whitespace() {
+                    return Err(ParseError::UnexpectedCharacter(i));
+                }
+            }
+            words.push(Word::Quoted(word));
+        } else {
+            let mut end = input.len();
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    end = i;
+                    break;
+                }
+                if c == '"' {
+                    return Err(ParseError::UnexpectedCharacter(i));
+                }
+                chars.next();
+            }
+            words.push(Word::Plain(&input[start..end]));
+        }
+    }
+
+    Ok(words)
+}
+
+/// Splits an S-expression into parentheses and words.
+///
+/// Words follow the same quoting rules as [`lex_words`], and are also delimited by parentheses.
+/// Each lexeme comes with its byte offset in the input.
+/// Fails on unterminated quotes, unknown escapes, or quotes within plain words.
+pub(crate) fn lex_sexpr(input: &str) -> Result<Vec<(usize, SexprLexeme<'_>)>, ParseError> {
+    let is_delimiter = |c: char| c.is_whitespace() || c == '(' || c == ')';
+    let mut lexemes = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            chars.next();
+            let parenthesis = if c == '(' {
+                Parenthesis::Open
+            } else {
+                Parenthesis::Close
+            };
+            lexemes.push((start, SexprLexeme::Parenthesis(parenthesis)));
+        } else if c == '"' {
+            let word = read_quoted(&mut chars)?;
+            if let Some(&(i, c)) = chars.peek() {
+                if !is_delimiter(c) {
+                    return Err(ParseError::UnexpectedCharacter(i));
+                }
+            }
+            lexemes.push((start, SexprLexeme::Word(Word::Quoted(word))));
+        } else {
+            let mut end = input.len();
+            while let Some(&(i, c)) = chars.peek() {
+                if is_delimiter(c) {
+                    end = i;
+                    break;
+                }
+                if c == '"' {
+                    return Err(ParseError::UnexpectedCharacter(i));
+                }
+                chars.next();
+            }
+            lexemes.push((start, SexprLexeme::Word(Word::Plain(&input[start..end]))));
+        }
+    }
+
+    Ok(lexemes)
+}
+
+/// Reads a quoted word, starting at its opening quote.
+fn read_quoted(chars: &mut Peekable<CharIndices<'_>>) -> Result<String, ParseError> {
+    let (start, _) = chars.next().expect("read_quoted starts at a quote");
+    let unterminated = ParseError::UnterminatedQuote(start);
+    let mut word = String::new();
+    loop {
+        match chars.next().ok_or(unterminated)? {
+            (_, '"') => return Ok(word),
+            (i, '\\') => match chars.next().ok_or(unterminated)? {
+                (_, c @ ('"' | '\\')) => word.push(c),
+                _ => return Err(ParseError::InvalidEscape(i)),
+            },
+            (_, c) => word.push(c),
+        }
+    }
+}
+
+/// Quotes a word if needed, so that [`lex_words`] reads it back as a single predicate.
+pub(crate) fn quote_word(word: &str) -> Cow<'_, str> {
+    if needs_quotes(word) {
+        Cow::Owned(quote(word))
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+/// Quotes a word if needed, so that [`lex_sexpr`] reads it back as a single atom.
+pub(crate) fn quote_atom(word: &str) -> Cow<'_, str> {
+    if needs_quotes(word) || word.contains(['(', ')']) {
+        Cow::Owned(quote(word))
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+fn needs_quotes(word: &str) -> bool {
+    word.is_empty()
+        || word.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\')
+        || Operator::from_keyword(word).is_some()
+}
+
+fn quote(word: &str) -> String {
+    let mut quoted = String::with_capacity(word.len() + 2);
+    quoted.push('"');
+    for c in word.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lex() {
+        assert_eq!(
+            lex(" A AND(B  OR\tc1)"),
+            vec![
+                Lexeme::Word("A"),
+                Lexeme::Word("AND"),
+                Lexeme::Parenthesis(Parenthesis::Open),
+                Lexeme::Word("B"),
+                Lexeme::Word("OR"),
+                Lexeme::Word("c1"),
+                Lexeme::Parenthesis(Parenthesis::Close),
+            ]
+        );
+        assert!(lex("  ").is_empty());
+    }
+
+    #[test]
+    fn test_lex_words() {
+        assert_eq!(
+            lex_words(r#" a "b c"  "" "d\"e\\"	f(x) "#),
+            Ok(vec![
+                Word::Plain("a"),
+                Word::Quoted("b c".to_string()),
+                Word::Quoted(String::new()),
+                Word::Quoted("d\"e\\".to_string()),
+                Word::Plain("f(x)"),
+            ])
+        );
+        assert_eq!(lex_words(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_lex_words_invalid() {
+        for (input, error) in [
+            (r#"a "b"#, ParseError::UnterminatedQuote(2)),
+            (r#""\"#, ParseError::UnterminatedQuote(0)),
+            (r#""a\n""#, ParseError::InvalidEscape(2)),
+            (r#""a"b"#, ParseError::UnexpectedCharacter(3)),
+            (r#"a"b""#, ParseError::UnexpectedCharacter(1)),
+        ] {
+            assert_eq!(lex_words(input), Err(error), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_lex_sexpr() {
+        assert_eq!(
+            lex_sexpr(r#"(and a("b c")f(x)"#),
+            Ok(vec![
+                (0, SexprLexeme::Parenthesis(Parenthesis::Open)),
+                (1, SexprLexeme::Word(Word::Plain("and"))),
+                (5, SexprLexeme::Word(Word::Plain("a"))),
+                (6, SexprLexeme::Parenthesis(Parenthesis::Open)),
+                (7, SexprLexeme::Word(Word::Quoted("b c".to_string()))),
+                (12, SexprLexeme::Parenthesis(Parenthesis::Close)),
+                (13, SexprLexeme::Word(Word::Plain("f"))),
+                (14, SexprLexeme::Parenthesis(Parenthesis::Open)),
+                (15, SexprLexeme::Word(Word::Plain("x"))),
+                (16, SexprLexeme::Parenthesis(Parenthesis::Close)),
+            ])
+        );
+        for (input, error) in [
+            (r#"("a"#, ParseError::UnterminatedQuote(1)),
+            (r#"(a"b")"#, ParseError::UnexpectedCharacter(2)),
+            (r#""a"b"#, ParseError::UnexpectedCharacter(3)),
+            (r#"("\t")"#, ParseError::InvalidEscape(2)),
+        ] {
+            assert_eq!(lex_sexpr(input), Err(error), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_quote_word() {
+        assert_eq!(quote_word("a"), "a");
+        assert_eq!(quote_word("f(x)"), "f(x)");
+        assert_eq!(quote_word(""), r#""""#);
+        assert_eq!(quote_word("a b"), r#""a b""#);
+        assert_eq!(quote_word("and"), r#""and""#);
+        assert_eq!(quote_word("||"), r#""||""#);
+        assert_eq!(quote_word(r#"say "hi"\"#), r#""say \"hi\"\\""#);
+
+        for word in ["", "a b", "and", r#"say "hi"\"#, "\t"] {
+            assert_eq!(
+                lex_words(&quote_word(word)),
+                Ok(vec![Word::Quoted(word.to_string())])
+            );
+        }
+        assert_eq!(quote_atom("a"), "a");
+        assert_eq!(quote_atom("f(x)"), r#""f(x)""#);
+    }
+}
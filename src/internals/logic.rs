@@ -0,0 +1,226 @@
+use crate::{FuzzyLogic, Operator, Truth};
+
+/// The values an expression can evaluate to, and how operators combine them.
+pub(crate) trait Logic {
+    type Value: Copy;
+
+    fn apply(&self, op: Operator, operands: &[Self::Value]) -> Self::Value;
+
+    /// Returns the result of the operator if it's already determined by its first operand.
+    fn short_circuit(&self, op: Operator, first_operand: Self::Value) -> Option<Self::Value>;
+}
+
+pub(crate) struct Boolean;
+
+impl Logic for Boolean {
+    type Value = bool;
+
+    fn apply(&self, op: Operator, operands: &[bool]) -> bool {
+        op.apply(operands)
+    }
+
+    fn short_circuit(&self, op: Operator, first_operand: bool) -> Option<bool> {
+        op.short_circuit(first_operand)
+    }
+}
+
+/// Kleene's strong three-valued logic.
+pub(crate) struct Kleene;
+
+impl Logic for Kleene {
+    type Value = Truth;
+
+    fn apply(&self, op: Operator, operands: &[Truth]) -> Truth {
+        let xor = |a: Truth, b: Truth| (a | b) & !(a & b);
+        match (op, operands) {
+            (Operator::Not, [a]) => !*a,
+            (Operator::And, [a, b]) => *a & *b,
+            (Operator::Or, [a, b]) => *a | *b,
+            (Operator::Xor, [a, b]) => xor(*a, *b),
+            (Operator::Nand, [a, b]) => !(*a & *b),
+            (Operator::Nor, [a, b]) => !(*a | *b),
+            (Operator::Implies, [a, b]) => !*a | *b,
+            (Operator::Iff, [a, b]) => !xor(*a, *b),
+            _ => unreachable!("wrong number of operands for {op:?}"),
+        }
+    }
+
+    fn short_circuit(&self, op: Operator, first_operand: Truth) -> Option<Truth> {
+        op.short_circuit(first_operand.to_bool()?).map(Truth::from)
+    }
+}
+
+impl Logic for FuzzyLogic {
+    type Value = f64;
+
+    fn apply(&self, op: Operator, operands: &[f64]) -> f64 {
+        let xor = |a: f64, b: f64| self.t_conorm(self.t_norm(a, 1.0 - b), self.t_norm(1.0 - a, b));
+        match (op, operands) {
+            (Operator::Not, [a]) => 1.0 - a,
+            (Operator::And, [a, b]) => self.t_norm(*a, *b),
+            (Operator::Or, [a, b]) => self.t_conorm(*a, *b),
+            (Operator::Xor, [a, b]) => xor(*a, *b),
+            (Operator::Nand, [a, b]) => 1.0 - self.t_norm(*a, *b),
+            (Operator::Nor, [a, b]) => 1.0 - self.t_conorm(*a, *b),
+            (Operator::Implies, [a, b]) => self.t_conorm(1.0 - a, *b),
+            (Operator::Iff, [a, b]) => 1.0 - xor(*a, *b),
+            _ => unreachable!("wrong number of operands for {op:?}"),
+        }
+    }
+
+    // every t-norm has 0 as absorbing element, and every t-conorm has 1
+    fn short_circuit(&self, op: Operator, first_operand: f64) -> Option<f64> {
+        op.short_circuit(crisp(first_operand)?).map(f64::from)
+    }
+}
+
+/// Probabilities of independent events.
+pub(crate) struct Probability;
+
+impl Logic for Probability {
+    type Value = f64;
+
+    fn apply(&self, op: Operator, operands: &[f64]) -> f64 {
+        let xor = |a: f64, b: f64| a + b - 2.0 * a * b;
+        match (op, operands) {
+            (Operator::Not, [a]) => 1.0 - a,
+            (Operator::And, [a, b]) => a * b,
+            (Operator::Or, [a, b]) => a + b - a * b,
+            (Operator::Xor, [a, b]) => xor(*a, *b),
+            (Operator::Nand, [a, b]) => 1.0 - a * b,
+            (Operator::Nor, [a, b]) => 1.0 - (a + b - a * b),
+            (Operator::Implies, [a, b]) => 1.0 - a + a * b,
+            (Operator::Iff, [a, b]) => 1.0 - xor(*a, *b),
+            _ => unreachable!("wrong number of operands for {op:?}"),
+        }
+    }
+
+    fn short_circuit(&self, op: Operator, first_operand: f64) -> Option<f64> {
+        op.short_circuit(crisp(first_operand)?).map(f64::from)
+    }
+}
+
+/// Returns the boolean value of a degree of truth that is exactly 0 or 1.
+fn crisp(value: f64) -> Option<bool> {
+    if value == 0.0 {
+        Some(false)
+    } else if value == 1.0 {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Brings a score into [0, 1], treating NaN as 0.
+pub(crate) fn clamp_score(score: f64) -> f64 {
+    if score.is_nan() {
+        0.0
+    } else {
+        score.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // known operands give the same results as boolean logic
+    fn test_kleene_known() {
+        for op in Operator::ALL {
+            for bits in 0..4 {
+                let operands = [bits & 1 != 0, bits & 2 != 0];
+                let operands = &operands[..op.arity()];
+                let truths: Vec<Truth> = operands.iter().map(|&b| Truth::from(b)).collect();
+                assert_eq!(
+                    Kleene.apply(op, &truths),
+                    Truth::from(Boolean.apply(op, operands))
+                );
+            }
+        }
+    }
+
+    #[test]
+    // an unknown operand gives an unknown result, unless the other operand determines it
+    fn test_kleene_unknown() {
+        for op in Operator::ALL {
+            for position in 0..op.arity() {
+                for other in [false, true] {
+                    let mut operands = [Truth::from(other); 2];
+                    operands[position] = Truth::Unknown;
+                    let results: Vec<bool> = [false, true]
+                        .into_iter()
+                        .map(|value| {
+                            let mut operands = [other; 2];
+                            operands[position] = value;
+                            op.apply(&operands[..op.arity()])
+                        })
+                        .collect();
+                    let expected = if results[0] == results[1] {
+                        Truth::from(results[0])
+                    } else {
+                        Truth::Unknown
+                    };
+                    assert_eq!(Kleene.apply(op, &operands[..op.arity()]), expected);
+                }
+            }
+        }
+        assert_eq!(
+            Kleene.apply(Operator::Xor, &[Truth::Unknown, Truth::Unknown]),
+            Truth::Unknown
+        );
+    }
+
+    #[test]
+    // every family agrees with boolean logic on 0 and 1, and stays within [0, 1]
+    fn test_fuzzy() {
+        let families = [
+            FuzzyLogic::MinMax,
+            FuzzyLogic::Product,
+            FuzzyLogic::Lukasiewicz,
+        ];
+        for logic in families {
+            for op in Operator::ALL {
+                for bits in 0..4 {
+                    let operands = [bits & 1 != 0, bits & 2 != 0];
+                    let operands = &operands[..op.arity()];
+                    let scores: Vec<f64> = operands.iter().map(|&b| f64::from(b)).collect();
+                    assert_eq!(
+                        logic.apply(op, &scores),
+                        f64::from(Boolean.apply(op, operands))
+                    );
+                }
+                for a in [0.0, 0.25, 0.5, 0.75, 1.0] {
+                    for b in [0.0, 0.3, 0.6, 1.0] {
+                        let score = logic.apply(op, &[a, b][..op.arity()]);
+                        assert!((0.0..=1.0).contains(&score), "{logic:?} {op:?} {a} {b}");
+                    }
+                }
+            }
+        }
+        assert_eq!(FuzzyLogic::MinMax.apply(Operator::And, &[0.25, 0.5]), 0.25);
+        assert_eq!(FuzzyLogic::Product.apply(Operator::Or, &[0.5, 0.5]), 0.75);
+        assert_eq!(
+            FuzzyLogic::Lukasiewicz.apply(Operator::And, &[0.25, 0.5]),
+            0.0
+        );
+    }
+
+    #[test]
+    // with crisp operands, probabilities give the same results as boolean logic
+    fn test_probability() {
+        for op in Operator::ALL {
+            for bits in 0..4 {
+                let operands = [bits & 1 != 0, bits & 2 != 0];
+                let operands = &operands[..op.arity()];
+                let probabilities: Vec<f64> = operands.iter().map(|&b| f64::from(b)).collect();
+                assert_eq!(
+                    Probability.apply(op, &probabilities),
+                    f64::from(Boolean.apply(op, operands))
+                );
+            }
+        }
+        assert_eq!(Probability.apply(Operator::Xor, &[0.5, 0.25]), 0.5);
+        assert_eq!(Probability.apply(Operator::Implies, &[0.5, 0.25]), 0.625);
+    }
+}
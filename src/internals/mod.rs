@@ -1,2 +1,10 @@
+pub(crate) mod bit_stack;
+pub(crate) mod codec;
+pub(crate) mod display_with;
 pub(crate) mod infix_stack_item;
-pub(crate) mod postfix_stack_item;
+pub(crate) mod infix_writer;
+pub(crate) mod instruction;
+pub(crate) mod into_ok;
+pub(crate) mod lexer;
+pub(crate) mod logic;
+pub(crate) mod operand_parent;
@@ -0,0 +1,79 @@
+use crate::PostfixToken;
+
+/// The operator consuming a sub-expression, identified by the index of its last token.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) struct OperandParent {
+    pub(crate) operator_index: usize,
+    pub(crate) is_first_operand: bool,
+}
+
+/// Returns, for each token of a valid postfix expression, the operator consuming
+/// the sub-expression ending at that token (`None` for the root).
+pub(crate) fn operand_parents<Predicate>(
+    tokens: &[PostfixToken<Predicate>],
+) -> Vec<Option<OperandParent>> {
+    let mut parents = Vec::new();
+    fill_operand_parents(tokens, &mut parents, &mut Vec::new());
+    parents
+}
+
+/// Like [`operand_parents`], but writes into existing buffers to avoid allocating.
+pub(crate) fn fill_operand_parents<Predicate>(
+    tokens: &[PostfixToken<Predicate>],
+    parents: &mut Vec<Option<OperandParent>>,
+    ends: &mut Vec<usize>,
+) {
+    parents.clear();
+    parents.resize(tokens.len(), None);
+    ends.clear();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let PostfixToken::Operator(op) = token {
+            let first = ends.len() - op.arity();
+            for (j, end) in ends.drain(first..).enumerate() {
+                parents[end] = Some(OperandParent {
+                    operator_index: i,
+                    is_first_operand: j == 0,
+                });
+            }
+        }
+        ends.push(i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Operator;
+
+    #[test]
+    // abc+!*
+    fn test_operand_parents() {
+        let tokens = [
+            PostfixToken::Predicate('a'),
+            PostfixToken::Predicate('b'),
+            PostfixToken::Predicate('c'),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Operator(Operator::And),
+        ];
+        let parent = |operator_index, is_first_operand| {
+            Some(OperandParent {
+                operator_index,
+                is_first_operand,
+            })
+        };
+
+        assert_eq!(
+            operand_parents(&tokens),
+            vec![
+                parent(5, true),
+                parent(3, true),
+                parent(3, false),
+                parent(4, true),
+                parent(5, false),
+                None,
+            ]
+        );
+    }
+}
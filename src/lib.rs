@@ -1,9 +1,22 @@
 pub use enums::{
-    infix_token::InfixToken, operator::Operator, parenthesis::Parenthesis,
-    postfix_token::PostfixToken,
+    decode_error::DecodeError, expression_tree::ExpressionTree, fuzzy_logic::FuzzyLogic,
+    infix_token::InfixToken, keyword_style::KeywordStyle, operator::Operator,
+    parenthesis::Parenthesis, parse_error::ParseError, partial_evaluation::PartialEvaluation,
+    postfix_token::PostfixToken, prefix_token::PrefixToken, table_style::TableStyle,
+    trace_step::TraceStep, truth::Truth, validation_error::ValidationError,
+};
+pub use structs::{
+    bit_set::BitSet, compiled_expression::CompiledExpression, eval_context::EvalContext,
+    infix_expression::InfixExpression, postfix_expression::PostfixExpression,
+    predicate_error::PredicateError, prefix_expression::PrefixExpression, trace::Trace,
+    truth_table::TruthTable,
+};
+pub use traits::{
+    partial_predicate_evaluator::PartialPredicateEvaluator, predicate_encoder::PredicateEncoder,
+    predicate_evaluator::PredicateEvaluator, scoring_evaluator::ScoringEvaluator,
+    tri_state_predicate_evaluator::TriStatePredicateEvaluator,
+    try_predicate_evaluator::TryPredicateEvaluator,
 };
-pub use structs::{infix_expression::InfixExpression, postfix_expression::PostfixExpression};
-pub use traits::predicate_evaluator::PredicateEvaluator;
 
 mod enums;
 mod internals;
@@ -0,0 +1,169 @@
+use std::iter::FromIterator;
+
+/// A fixed-size set of bits, stored in 64-bit words.
+///
+/// Bit `i` is stored in word `i / 64`, at position `i % 64`; bits past the length
+/// are always zero.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates a set of `len` bits, all cleared.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Creates a set of `len` bits, where bit `i` is given by `f(i)`.
+    pub fn from_fn(len: usize, mut f: impl FnMut(usize) -> bool) -> Self {
+        let mut bits = Self::new(len);
+        for i in 0..len {
+            if f(i) {
+                bits.words[i / 64] |= 1 << (i % 64);
+            }
+        }
+        bits
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns bit `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    #[must_use]
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "bit {i} out of bounds for length {}",
+            self.len
+        );
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Sets bit `i` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(
+            i < self.len,
+            "bit {i} out of bounds for length {}",
+            self.len
+        );
+        if value {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    /// Returns the number of bits set.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Iterates over the indices of the bits set, in increasing order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// Iterates over all the bits, in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    #[must_use]
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub(crate) fn from_words(words: Vec<u64>, len: usize) -> Self {
+        let mut bits = Self { words, len };
+        bits.clear_padding();
+        bits
+    }
+
+    /// Clears the bits of the last word that are past the length.
+    fn clear_padding(&mut self) {
+        if let Some(last) = self.words.last_mut() {
+            if !self.len.is_multiple_of(64) {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+    }
+}
+
+impl FromIterator<bool> for BitSet {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = Self::default();
+        for value in iter {
+            if bits.len.is_multiple_of(64) {
+                bits.words.push(0);
+            }
+            if value {
+                bits.words[bits.len / 64] |= 1 << (bits.len % 64);
+            }
+            bits.len += 1;
+        }
+        bits
+    }
+}
+
+/// Deserializes the set from its words and length, rejecting a number of words that doesn't
+/// match the length, or bits set past the length.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BitSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Fields {
+            words: Vec<u64>,
+            len: usize,
+        }
+
+        let Fields { words, len } = Fields::deserialize(deserializer)?;
+        if words.len() != len.div_ceil(64) {
+            return Err(serde::de::Error::custom(format!(
+                "expected {} words for {len} bits, got {}",
+                len.div_ceil(64),
+                words.len()
+            )));
+        }
+        let padding = words.last().map_or(0, |last| match len % 64 {
+            0 => 0,
+            used => last >> used,
+        });
+        if padding != 0 {
+            return Err(serde::de::Error::custom("bits set past the length"));
+        }
+        Ok(Self { words, len })
+    }
+}
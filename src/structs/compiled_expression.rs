@@ -0,0 +1,249 @@
+use crate::internals::bit_stack::BitStack;
+use crate::internals::instruction::Instruction;
+use crate::internals::into_ok::into_ok;
+use crate::internals::operand_parent::operand_parents;
+use crate::traits::predicate_evaluator::PredicateEvaluator;
+use crate::traits::try_predicate_evaluator::TryPredicateEvaluator;
+use crate::{Operator, PostfixExpression, PostfixToken};
+use std::convert::Infallible;
+
+/// An expression lowered into a bytecode, for fast repeated evaluation.
+///
+/// `AND`, `OR`, `NAND`, `NOR` and `IMPLIES` are compiled into conditional jumps,
+/// so that the second operand is skipped whenever the first one determines the result.
+/// Predicates are therefore evaluated in the same order as [`PostfixExpression::evaluate`].
+/// Evaluation doesn't allocate, unless `XOR` and `IFF` are nested more than 128 levels deep.
+#[derive(Debug, PartialEq)]
+pub struct CompiledExpression<Predicate> {
+    predicates: Vec<Predicate>,
+    code: Vec<Instruction>,
+}
+
+impl<Predicate> CompiledExpression<Predicate> {
+    /// Returns the predicates of the expression, in token order.
+    #[must_use]
+    pub fn predicates(&self) -> &[Predicate] {
+        &self.predicates
+    }
+
+    pub fn evaluate(&self, evaluator: &dyn PredicateEvaluator<Predicate = Predicate>) -> bool {
+        into_ok(self.evaluate_with(|p| Ok::<_, Infallible>(evaluator.evaluate_predicate(p))))
+    }
+
+    /// Evaluates the expression like [`CompiledExpression::evaluate`], but with an evaluator
+    /// that can fail.
+    ///
+    /// The first error returned by the evaluator is propagated,
+    /// and no other predicate is evaluated after it.
+    pub fn try_evaluate<E>(
+        &self,
+        evaluator: &dyn TryPredicateEvaluator<Predicate = Predicate, Error = E>,
+    ) -> Result<bool, E> {
+        self.evaluate_with(|p| evaluator.try_evaluate_predicate(p))
+    }
+
+    pub(crate) fn compile(tokens: Vec<PostfixToken<Predicate>>) -> Self {
+        // for each token, the operator whose first operand ends at that token
+        let first_operand_of: Vec<Option<Operator>> = operand_parents(&tokens)
+            .into_iter()
+            .map(|parent| match tokens[parent?.operator_index] {
+                PostfixToken::Operator(op) if parent?.is_first_operand => Some(op),
+                _ => None,
+            })
+            .collect();
+        let mut predicates = Vec::new();
+        let mut code = Vec::new();
+        // the jumps over the second operand of the operators being compiled
+        let mut pending_jumps: Vec<usize> = Vec::new();
+
+        for (token, parent) in tokens.into_iter().zip(first_operand_of) {
+            match token {
+                PostfixToken::Predicate(p) => {
+                    code.push(Instruction::Load(predicates.len()));
+                    predicates.push(p);
+                }
+                PostfixToken::Operator(op) => {
+                    let has_jump = matches!(
+                        op,
+                        Operator::And
+                            | Operator::Or
+                            | Operator::Nand
+                            | Operator::Nor
+                            | Operator::Implies
+                    );
+                    if has_jump {
+                        // the second operand is complete, so its jump lands here
+                        if let Some(jump) = pending_jumps.pop() {
+                            let offset = code.len() - jump - 1;
+                            code[jump] = match code[jump] {
+                                Instruction::JumpIfFalse(_) => Instruction::JumpIfFalse(offset),
+                                Instruction::JumpIfTrue(_) => Instruction::JumpIfTrue(offset),
+                                instruction => instruction,
+                            };
+                        }
+                    }
+                    match op {
+                        Operator::Not | Operator::Nand | Operator::Nor => {
+                            code.push(Instruction::Not);
+                        }
+                        Operator::Xor => code.push(Instruction::Xor),
+                        Operator::Iff => code.push(Instruction::Iff),
+                        Operator::And | Operator::Or | Operator::Implies => {}
+                    }
+                }
+            }
+
+            let Some(op) = parent else {
+                continue;
+            };
+            match op {
+                Operator::And | Operator::Nand => {
+                    pending_jumps.push(code.len());
+                    code.push(Instruction::JumpIfFalse(0));
+                }
+                Operator::Or | Operator::Nor => {
+                    pending_jumps.push(code.len());
+                    code.push(Instruction::JumpIfTrue(0));
+                }
+                Operator::Implies => {
+                    code.push(Instruction::Not);
+                    pending_jumps.push(code.len());
+                    code.push(Instruction::JumpIfTrue(0));
+                }
+                Operator::Xor | Operator::Iff => code.push(Instruction::Push),
+                Operator::Not => {}
+            }
+        }
+
+        thread_jumps(&mut code);
+        Self { predicates, code }
+    }
+
+    fn evaluate_with<E>(
+        &self,
+        mut evaluate_predicate: impl FnMut(&Predicate) -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        let mut value = false;
+        let mut stack = BitStack::default();
+        let mut pc = 0;
+
+        while let Some(instruction) = self.code.get(pc) {
+            pc += 1;
+            match *instruction {
+                Instruction::Load(i) => value = evaluate_predicate(&self.predicates[i])?,
+                Instruction::Not => value = !value,
+                Instruction::JumpIfFalse(offset) => {
+                    if !value {
+                        pc += offset;
+                    }
+                }
+                Instruction::JumpIfTrue(offset) => {
+                    if value {
+                        pc += offset;
+                    }
+                }
+                Instruction::Push => stack.push(value),
+                Instruction::Xor => value ^= stack.pop(),
+                Instruction::Iff => value = value == stack.pop(),
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+/// Retargets jumps landing on other jumps, which the register value at that point
+/// makes either always taken or never taken.
+fn thread_jumps(code: &mut [Instruction]) {
+    // jumps only go forward, so later jumps are already threaded
+    for i in (0..code.len()).rev() {
+        let (offset, jump_value) = match code[i] {
+            Instruction::JumpIfFalse(offset) => (offset, false),
+            Instruction::JumpIfTrue(offset) => (offset, true),
+            _ => continue,
+        };
+        let mut target = i + 1 + offset;
+        loop {
+            match code.get(target) {
+                Some(Instruction::JumpIfFalse(next)) if !jump_value => target += 1 + next,
+                Some(Instruction::JumpIfTrue(next)) if jump_value => target += 1 + next,
+                Some(Instruction::JumpIfFalse(_) | Instruction::JumpIfTrue(_)) => target += 1,
+                _ => break,
+            }
+        }
+        let offset = target - i - 1;
+        code[i] = if jump_value {
+            Instruction::JumpIfTrue(offset)
+        } else {
+            Instruction::JumpIfFalse(offset)
+        };
+    }
+}
+
+impl<Predicate> From<PostfixExpression<Predicate>> for CompiledExpression<Predicate> {
+    fn from(postfix: PostfixExpression<Predicate>) -> Self {
+        postfix.compile()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(rpn: &str) -> Vec<Instruction> {
+        let postfix = PostfixExpression::parse(rpn, |s| Some(s.to_string())).unwrap();
+        postfix.compile().code
+    }
+
+    #[test]
+    fn test_compile() {
+        assert_eq!(compile("a"), vec![Instruction::Load(0)]);
+        assert_eq!(
+            compile("a b IMPLIES"),
+            vec![
+                Instruction::Load(0),
+                Instruction::Not,
+                Instruction::JumpIfTrue(1),
+                Instruction::Load(1),
+            ]
+        );
+        assert_eq!(
+            compile("a b XOR c NAND"),
+            vec![
+                Instruction::Load(0),
+                Instruction::Push,
+                Instruction::Load(1),
+                Instruction::Xor,
+                Instruction::JumpIfFalse(1),
+                Instruction::Load(2),
+                Instruction::Not,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_threads_jumps() {
+        // the first jump goes straight to the end
+        assert_eq!(
+            compile("a b AND c AND"),
+            vec![
+                Instruction::Load(0),
+                Instruction::JumpIfFalse(3),
+                Instruction::Load(1),
+                Instruction::JumpIfFalse(1),
+                Instruction::Load(2),
+            ]
+        );
+        // when a is true, the jump skips the test of the OR
+        assert_eq!(
+            compile("a b OR c AND"),
+            vec![
+                Instruction::Load(0),
+                Instruction::JumpIfTrue(2),
+                Instruction::Load(1),
+                Instruction::JumpIfFalse(1),
+                Instruction::Load(2),
+            ]
+        );
+    }
+}
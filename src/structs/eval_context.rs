@@ -0,0 +1,26 @@
+use crate::internals::operand_parent::{fill_operand_parents, OperandParent};
+use crate::PostfixToken;
+
+/// Scratch buffers for evaluating postfix expressions, reusable across evaluations.
+///
+/// Passing the same context to [`PostfixExpression::evaluate_with`](crate::PostfixExpression::evaluate_with)
+/// over and over avoids allocating on every evaluation, once the buffers have grown
+/// to the size of the largest expression evaluated.
+#[derive(Debug, Default)]
+pub struct EvalContext {
+    pub(crate) stack: Vec<bool>,
+    pub(crate) parents: Vec<Option<OperandParent>>,
+    ends: Vec<usize>,
+}
+
+impl EvalContext {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepares the buffers for evaluating the given tokens.
+    pub(crate) fn prepare<Predicate>(&mut self, tokens: &[PostfixToken<Predicate>]) {
+        fill_operand_parents(tokens, &mut self.parents, &mut self.ends);
+    }
+}
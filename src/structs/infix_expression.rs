@@ -1,5 +1,16 @@
+use crate::internals::display_with::display_with;
 use crate::internals::infix_stack_item::InfixStackItem;
-use crate::{InfixToken, Parenthesis, PostfixExpression, PostfixToken};
+use crate::internals::infix_writer::write_infix;
+use crate::internals::into_ok::into_ok;
+use crate::internals::lexer::{lex, Lexeme};
+use crate::structs::predicate_error::PredicateError;
+use crate::{
+    ExpressionTree, InfixToken, KeywordStyle, Operator, Parenthesis, PostfixExpression,
+    PostfixToken, PrefixExpression, ValidationError,
+};
+use std::convert::Infallible;
+use std::fmt;
+use std::{slice, vec};
 
 #[derive(Debug, PartialEq)]
 pub struct InfixExpression<Predicate> {
@@ -9,7 +20,110 @@ pub struct InfixExpression<Predicate> {
 impl<Predicate> InfixExpression<Predicate> {
     #[must_use]
     pub fn from_tokens(tokens: Vec<InfixToken<Predicate>>) -> Option<Self> {
-        Self::are_tokens_valid(&tokens).then(|| Self { tokens })
+        Self::try_from_tokens(tokens).ok()
+    }
+
+    pub fn try_from_tokens(tokens: Vec<InfixToken<Predicate>>) -> Result<Self, ValidationError> {
+        Self::validate_tokens(&tokens).map(|()| Self { tokens })
+    }
+
+    #[must_use]
+    pub fn tokens(&self) -> &[InfixToken<Predicate>] {
+        &self.tokens
+    }
+
+    #[must_use]
+    pub fn into_tokens(self) -> Vec<InfixToken<Predicate>> {
+        self.tokens
+    }
+
+    /// Returns the number of tokens in the expression.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, InfixToken<Predicate>> {
+        self.tokens.iter()
+    }
+
+    /// Iterates over the predicates of the expression, in token order.
+    pub fn predicates(&self) -> impl Iterator<Item = &Predicate> {
+        self.tokens.iter().filter_map(|token| match token {
+            InfixToken::Predicate(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    /// Parses an infix expression from a string such as `A AND (B OR C)`.
+    ///
+    /// Parentheses and whitespace separate lexemes; lexemes matching an operator keyword
+    /// (case-insensitive) become operators, while any other lexeme is turned into a predicate
+    /// by `predicate_parser`.
+    /// Symbolic keywords such as `&&` or `!` must be separated from predicates by whitespace
+    /// or parentheses too: `a&&b` and `!a` are single lexemes, read as predicates.
+    ///
+    /// Returns `None` if `predicate_parser` rejects a lexeme or if the resulting tokens
+    /// don't form a valid expression.
+    pub fn parse<'a, F>(input: &'a str, mut predicate_parser: F) -> Option<Self>
+    where
+        F: FnMut(&'a str) -> Option<Predicate>,
+    {
+        let mut tokens = Vec::new();
+        for lexeme in lex(input) {
+            let token = match lexeme {
+                Lexeme::Parenthesis(parenthesis) => InfixToken::Parenthesis(parenthesis),
+                Lexeme::Word(word) => match Operator::from_keyword(word) {
+                    Some(op) => InfixToken::Operator(op),
+                    None => InfixToken::Predicate(predicate_parser(word)?),
+                },
+            };
+            tokens.push(token);
+        }
+        Self::from_tokens(tokens)
+    }
+
+    #[must_use]
+    pub fn map<Q>(self, mut f: impl FnMut(Predicate) -> Q) -> InfixExpression<Q> {
+        let tokens = self
+            .tokens
+            .into_iter()
+            .map(|token| into_ok(token.try_map(|p| Ok::<_, Infallible>(f(p)))))
+            .collect();
+        InfixExpression::from_tokens_unchecked(tokens)
+    }
+
+    /// Transforms every predicate of the expression, stopping at the first error.
+    ///
+    /// The returned error carries the index of the predicate that couldn't be transformed.
+    pub fn try_map<Q, E>(
+        self,
+        mut f: impl FnMut(Predicate) -> Result<Q, E>,
+    ) -> Result<InfixExpression<Q>, PredicateError<E>> {
+        let mut index = 0;
+        let tokens = self
+            .tokens
+            .into_iter()
+            .map(|token| {
+                token.try_map(|p| {
+                    let result = f(p).map_err(|e| PredicateError::new(index, e));
+                    index += 1;
+                    result
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(InfixExpression::from_tokens_unchecked(tokens))
+    }
+
+    #[must_use]
+    pub fn map_ref<Q>(&self, f: impl FnMut(&Predicate) -> Q) -> InfixExpression<Q> {
+        let tokens = self.tokens.iter().map(InfixToken::as_ref).collect();
+        InfixExpression::from_tokens_unchecked(tokens).map(f)
     }
 
     #[must_use]
@@ -22,10 +136,15 @@ impl<Predicate> InfixExpression<Predicate> {
                 InfixToken::Predicate(p) => {
                     output_queue.push(PostfixToken::Predicate(p));
                 }
+                InfixToken::Operator(op) if op.is_unary() => {
+                    stack.push(InfixStackItem::Operator(op));
+                }
                 InfixToken::Operator(op) => {
                     let precedence = op.precedence();
                     while let Some(InfixStackItem::Operator(stack_op)) = stack.last() {
-                        if precedence > stack_op.precedence() {
+                        if precedence > stack_op.precedence()
+                            || (precedence == stack_op.precedence() && op.is_right_associative())
+                        {
                             break;
                         }
                         output_queue.push(PostfixToken::Operator(*stack_op));
@@ -54,63 +173,122 @@ impl<Predicate> InfixExpression<Predicate> {
         PostfixExpression::from_tokens_unchecked(output_queue)
     }
 
+    #[must_use]
+    pub fn to_prefix(self) -> PrefixExpression<Predicate> {
+        self.to_postfix().to_prefix()
+    }
+
+    #[must_use]
+    pub fn to_tree(self) -> ExpressionTree<Predicate> {
+        self.to_postfix().to_tree()
+    }
+
+    /// Renders the expression using the given keyword style.
+    pub fn display(&self, style: KeywordStyle) -> impl fmt::Display + '_
+    where
+        Predicate: fmt::Display,
+    {
+        display_with(move |f| write_infix(f, &self.tokens, style, |f, p| write!(f, "{p}")))
+    }
+
     pub(crate) fn from_tokens_unchecked(tokens: Vec<InfixToken<Predicate>>) -> Self {
         Self { tokens }
     }
 
-    fn are_tokens_valid(tokens: &[InfixToken<Predicate>]) -> bool {
-        let mut operator_stack: Vec<InfixStackItem> = Vec::new();
-        let mut predicate_cnt: usize = 0;
+    fn validate_tokens(tokens: &[InfixToken<Predicate>]) -> Result<(), ValidationError> {
+        let mut open_parenthesis: Vec<usize> = Vec::new();
         let mut predicate_expected = true;
 
-        for token in tokens {
+        for (i, token) in tokens.iter().enumerate() {
             match token {
                 InfixToken::Predicate(_) => {
                     if !predicate_expected {
-                        return false;
+                        return Err(ValidationError::AdjacentPredicates(i));
                     }
-                    predicate_cnt += 1;
                     predicate_expected = false;
                 }
-                InfixToken::Operator(op) => {
-                    operator_stack.push(InfixStackItem::Operator(*op));
+                InfixToken::Operator(op) if op.is_unary() => {
+                    if !predicate_expected {
+                        return Err(ValidationError::MissingOperator(i));
+                    }
+                }
+                InfixToken::Operator(_) => {
+                    if predicate_expected {
+                        return Err(ValidationError::MissingOperand(i));
+                    }
                     predicate_expected = true;
                 }
                 InfixToken::Parenthesis(Parenthesis::Open) => {
-                    operator_stack.push(InfixStackItem::Parenthesis(Parenthesis::Open));
+                    if !predicate_expected {
+                        return Err(ValidationError::MissingOperator(i));
+                    }
+                    open_parenthesis.push(i);
                 }
                 InfixToken::Parenthesis(Parenthesis::Close) => {
-                    while let Some(InfixStackItem::Operator(_)) = operator_stack.last() {
-                        operator_stack.pop();
-                        if predicate_cnt < 2 {
-                            return false;
-                        }
-                        predicate_cnt -= 1;
+                    if predicate_expected {
+                        return Err(ValidationError::MissingOperand(i));
                     }
-                    if operator_stack.last()
-                        != Some(&InfixStackItem::Parenthesis(Parenthesis::Open))
-                    {
-                        return false;
+                    if open_parenthesis.pop().is_none() {
+                        return Err(ValidationError::UnbalancedParenthesis(i));
                     }
-                    operator_stack.pop();
                 }
             }
         }
 
-        while let Some(item) = operator_stack.pop() {
-            match item {
-                InfixStackItem::Operator(_) => {
-                    if predicate_cnt < 2 {
-                        return false;
-                    }
-                    predicate_cnt -= 1;
-                }
-                InfixStackItem::Parenthesis(_) => {
-                    return false;
-                }
+        match tokens.last() {
+            None => Err(ValidationError::EmptyExpression),
+            Some(InfixToken::Operator(_)) => {
+                Err(ValidationError::TrailingOperator(tokens.len() - 1))
             }
+            Some(_) => match open_parenthesis.first() {
+                Some(i) => Err(ValidationError::UnbalancedParenthesis(*i)),
+                None if predicate_expected => Err(ValidationError::MissingOperand(tokens.len())),
+                None => Ok(()),
+            },
         }
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for InfixExpression<Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(KeywordStyle::default()).fmt(f)
+    }
+}
+
+impl<Predicate> IntoIterator for InfixExpression<Predicate> {
+    type Item = InfixToken<Predicate>;
+    type IntoIter = vec::IntoIter<InfixToken<Predicate>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.into_iter()
+    }
+}
+
+impl<'a, Predicate> IntoIterator for &'a InfixExpression<Predicate> {
+    type Item = &'a InfixToken<Predicate>;
+    type IntoIter = slice::Iter<'a, InfixToken<Predicate>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
+    }
+}
+
+/// Serializes the expression as its sequence of tokens.
+#[cfg(feature = "serde")]
+impl<Predicate: serde::Serialize> serde::Serialize for InfixExpression<Predicate> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.tokens.serialize(serializer)
+    }
+}
 
-        predicate_cnt == 1 && operator_stack.is_empty()
+/// Deserializes the expression from its sequence of tokens, rejecting invalid expressions
+/// as [`InfixExpression::try_from_tokens`] does.
+#[cfg(feature = "serde")]
+impl<'de, Predicate: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for InfixExpression<Predicate>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tokens = Vec::<InfixToken<Predicate>>::deserialize(deserializer)?;
+        Self::try_from_tokens(tokens).map_err(serde::de::Error::custom)
     }
 }
@@ -1,2 +1,9 @@
+pub mod bit_set;
+pub mod compiled_expression;
+pub mod eval_context;
 pub mod infix_expression;
 pub mod postfix_expression;
+pub mod predicate_error;
+pub mod prefix_expression;
+pub mod trace;
+pub mod truth_table;
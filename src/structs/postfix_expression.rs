@@ -1,8 +1,31 @@
 use crate::enums::postfix_token::PostfixToken;
-use crate::internals::postfix_stack_item::PostfixStackItem;
+use crate::internals::codec::{decode_tokens, encode_tokens};
+use crate::internals::display_with::display_with;
+use crate::internals::infix_writer::write_infix;
+use crate::internals::into_ok::into_ok;
+use crate::internals::lexer::{lex_sexpr, lex_words, quote_atom, quote_word, SexprLexeme, Word};
+use crate::internals::logic::{clamp_score, Boolean, Kleene, Logic, Probability};
+use crate::internals::operand_parent::{operand_parents, OperandParent};
+use crate::structs::predicate_error::PredicateError;
+use crate::traits::partial_predicate_evaluator::PartialPredicateEvaluator;
+use crate::traits::predicate_encoder::PredicateEncoder;
 use crate::traits::predicate_evaluator::PredicateEvaluator;
-use crate::{InfixExpression, InfixToken, Operator, Parenthesis};
-use std::collections::VecDeque;
+use crate::traits::scoring_evaluator::ScoringEvaluator;
+use crate::traits::tri_state_predicate_evaluator::TriStatePredicateEvaluator;
+use crate::traits::try_predicate_evaluator::TryPredicateEvaluator;
+use crate::{
+    BitSet, CompiledExpression, DecodeError, EvalContext, ExpressionTree, FuzzyLogic,
+    InfixExpression, InfixToken, KeywordStyle, Operator, Parenthesis, ParseError,
+    PartialEvaluation, PrefixExpression, PrefixToken, Trace, TraceStep, Truth, TruthTable,
+    ValidationError,
+};
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::{slice, vec};
 
 #[derive(Debug, PartialEq)]
 pub struct PostfixExpression<Predicate> {
@@ -12,89 +35,844 @@ pub struct PostfixExpression<Predicate> {
 impl<Predicate> PostfixExpression<Predicate> {
     #[must_use]
     pub fn from_tokens(tokens: Vec<PostfixToken<Predicate>>) -> Option<Self> {
-        Self::are_tokens_valid(&tokens).then(|| Self { tokens })
+        Self::try_from_tokens(tokens).ok()
+    }
+
+    pub fn try_from_tokens(tokens: Vec<PostfixToken<Predicate>>) -> Result<Self, ValidationError> {
+        Self::validate_tokens(&tokens).map(|()| Self { tokens })
+    }
+
+    /// Parses a postfix expression written in Reverse Polish Notation, such as `A B C OR AND`.
+    ///
+    /// Tokens are separated by whitespace; unquoted tokens matching an operator keyword
+    /// (case-insensitive) become operators, while any other token is turned into a predicate
+    /// by `predicate_parser`.
+    /// Predicates can be enclosed in double quotes, using `\"` and `\\` to escape
+    /// double quotes and backslashes.
+    ///
+    /// Fails if the input is malformed, if `predicate_parser` rejects a predicate,
+    /// or if the resulting tokens don't form a valid expression.
+    pub fn parse<F>(input: &str, mut predicate_parser: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(&str) -> Option<Predicate>,
+    {
+        let mut tokens = Vec::new();
+        let mut predicate_index = 0;
+        for word in lex_words(input)? {
+            let predicate = match word {
+                Word::Plain(word) => match Operator::from_keyword(word) {
+                    Some(op) => {
+                        tokens.push(PostfixToken::Operator(op));
+                        continue;
+                    }
+                    None => predicate_parser(word),
+                },
+                Word::Quoted(word) => predicate_parser(&word),
+            };
+            let predicate = predicate.ok_or(ParseError::InvalidPredicate(predicate_index))?;
+            predicate_index += 1;
+            tokens.push(PostfixToken::Predicate(predicate));
+        }
+        Self::try_from_tokens(tokens).map_err(ParseError::Invalid)
+    }
+
+    /// Writes the expression in Reverse Polish Notation, with tokens separated by single spaces.
+    ///
+    /// Predicates are rendered by `predicate_writer` and quoted when needed,
+    /// so that the output can be read back by [`PostfixExpression::parse`].
+    pub fn to_rpn(&self, mut predicate_writer: impl FnMut(&Predicate) -> String) -> String {
+        let mut rpn = String::new();
+        for token in &self.tokens {
+            if !rpn.is_empty() {
+                rpn.push(' ');
+            }
+            match token {
+                PostfixToken::Operator(op) => rpn.push_str(op.keyword(KeywordStyle::Words)),
+                PostfixToken::Predicate(p) => rpn.push_str(&quote_word(&predicate_writer(p))),
+            }
+        }
+        rpn
+    }
+
+    /// Parses a postfix expression written as an S-expression, such as `(and A (or B C))`.
+    ///
+    /// Each list starts with an operator keyword (case-insensitive) followed by its operands,
+    /// and any other atom is turned into a predicate by `predicate_parser`.
+    /// Associative operators (`and`, `or`, `xor`, `iff`) accept two or more operands,
+    /// which are combined from left to right. Atoms follow the same quoting rules as
+    /// [`PostfixExpression::parse`].
+    ///
+    /// Fails if the input is malformed, if an unquoted atom is an operator keyword,
+    /// or if `predicate_parser` rejects a predicate.
+    pub fn parse_sexpr<F>(input: &str, mut predicate_parser: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(&str) -> Option<Predicate>,
+    {
+        // the lists still open, with the number of operands read so far
+        let mut lists: Vec<(Operator, usize)> = Vec::new();
+        let mut tokens = Vec::new();
+        let mut predicate_index = 0;
+        let mut lexemes = lex_sexpr(input)?.into_iter();
+
+        while let Some((offset, lexeme)) = lexemes.next() {
+            let is_operand = !matches!(lexeme, SexprLexeme::Parenthesis(Parenthesis::Close));
+            let expects_operand = match lists.last() {
+                None => tokens.is_empty(),
+                Some((op, count)) => op.is_associative() || *count < op.arity(),
+            };
+            if is_operand && !expects_operand {
+                return Err(ParseError::UnexpectedToken(offset));
+            }
+
+            match lexeme {
+                SexprLexeme::Parenthesis(Parenthesis::Open) => {
+                    let (offset, keyword) = match lexemes.next() {
+                        Some((offset, SexprLexeme::Word(Word::Plain(keyword)))) => {
+                            (offset, keyword)
+                        }
+                        Some((offset, _)) => return Err(ParseError::UnexpectedToken(offset)),
+                        None => return Err(ParseError::UnexpectedEnd),
+                    };
+                    let op = Operator::from_keyword(keyword)
+                        .ok_or(ParseError::UnexpectedToken(offset))?;
+                    lists.push((op, 0));
+                    continue;
+                }
+                SexprLexeme::Parenthesis(Parenthesis::Close) => {
+                    let (op, count) = lists.pop().ok_or(ParseError::UnexpectedToken(offset))?;
+                    if op.is_associative() {
+                        if count < 2 {
+                            return Err(ParseError::UnexpectedToken(offset));
+                        }
+                    } else if count != op.arity() {
+                        return Err(ParseError::UnexpectedToken(offset));
+                    } else {
+                        tokens.push(PostfixToken::Operator(op));
+                    }
+                }
+                SexprLexeme::Word(word) => {
+                    let predicate = match word {
+                        Word::Plain(word) => {
+                            if Operator::from_keyword(word).is_some() {
+                                return Err(ParseError::UnexpectedToken(offset));
+                            }
+                            predicate_parser(word)
+                        }
+                        Word::Quoted(word) => predicate_parser(&word),
+                    };
+                    let predicate =
+                        predicate.ok_or(ParseError::InvalidPredicate(predicate_index))?;
+                    predicate_index += 1;
+                    tokens.push(PostfixToken::Predicate(predicate));
+                }
+            }
+
+            // an operand has just been read
+            if let Some((op, count)) = lists.last_mut() {
+                *count += 1;
+                if op.is_associative() && *count >= 2 {
+                    tokens.push(PostfixToken::Operator(*op));
+                }
+            }
+        }
+
+        if !lists.is_empty() {
+            return Err(ParseError::UnexpectedEnd);
+        }
+        Self::try_from_tokens(tokens).map_err(ParseError::Invalid)
+    }
+
+    /// Writes the expression as an S-expression, such as `(and A (or B C))`.
+    ///
+    /// Left-nested chains of the same associative operator are flattened into a single list,
+    /// so `A B AND C AND` is written `(and A B C)`, while `A B C AND AND` is written
+    /// `(and A (and B C))`; this way, reading the output back gives an equal expression.
+    /// Predicates are rendered by `predicate_writer` and quoted when needed,
+    /// so that the output can be read back by [`PostfixExpression::parse_sexpr`].
+    pub fn to_sexpr(&self, mut predicate_writer: impl FnMut(&Predicate) -> String) -> String {
+        // operands are kept as list elements, so that they can be flattened into their parent
+        let mut stack: Vec<(Option<Operator>, Vec<String>)> = Vec::new();
+        let render = |(op, elements): (Option<Operator>, Vec<String>)| match op {
+            Some(_) => format!("({})", elements.join(" ")),
+            None => elements.concat(),
+        };
+
+        for token in &self.tokens {
+            match token {
+                PostfixToken::Predicate(p) => {
+                    let atom = quote_atom(&predicate_writer(p)).into_owned();
+                    stack.push((None, vec![atom]));
+                }
+                PostfixToken::Operator(op) => {
+                    let mut elements = vec![op.keyword(KeywordStyle::Words).to_ascii_lowercase()];
+                    let operands = stack.split_off(stack.len() - op.arity());
+                    for (i, operand) in operands.into_iter().enumerate() {
+                        if i == 0 && op.is_associative() && operand.0 == Some(*op) {
+                            elements.extend(operand.1.into_iter().skip(1));
+                        } else {
+                            elements.push(render(operand));
+                        }
+                    }
+                    stack.push((Some(*op), elements));
+                }
+            }
+        }
+
+        render(stack.remove(0))
+    }
+
+    /// Lowers the expression into a bytecode, for fast repeated evaluation.
+    #[must_use]
+    pub fn compile(self) -> CompiledExpression<Predicate> {
+        CompiledExpression::compile(self.tokens)
+    }
+
+    /// Encodes the expression in a compact, versioned binary format,
+    /// using `encoder` to turn predicates into bytes.
+    ///
+    /// The output starts with a header holding the format version and ends with a checksum,
+    /// and can be read back by [`PostfixExpression::decode`].
+    #[must_use]
+    pub fn encode(&self, encoder: &dyn PredicateEncoder<Predicate = Predicate>) -> Vec<u8> {
+        encode_tokens(&self.tokens, encoder)
+    }
+
+    /// Decodes an expression written by [`PostfixExpression::encode`],
+    /// using `encoder` to read predicates back.
+    ///
+    /// Truncated or corrupted input is rejected, and the decoded tokens go through
+    /// the same validation as [`PostfixExpression::try_from_tokens`].
+    pub fn decode(
+        bytes: &[u8],
+        encoder: &dyn PredicateEncoder<Predicate = Predicate>,
+    ) -> Result<Self, DecodeError> {
+        let tokens = decode_tokens(bytes, encoder)?;
+        Self::try_from_tokens(tokens).map_err(DecodeError::InvalidExpression)
+    }
+
+    #[must_use]
+    pub fn tokens(&self) -> &[PostfixToken<Predicate>] {
+        &self.tokens
+    }
+
+    #[must_use]
+    pub fn into_tokens(self) -> Vec<PostfixToken<Predicate>> {
+        self.tokens
+    }
+
+    /// Returns the number of tokens in the expression.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, PostfixToken<Predicate>> {
+        self.tokens.iter()
+    }
+
+    /// Iterates over the predicates of the expression, in token order.
+    pub fn predicates(&self) -> impl Iterator<Item = &Predicate> {
+        self.tokens.iter().filter_map(|token| match token {
+            PostfixToken::Predicate(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn map<Q>(self, mut f: impl FnMut(Predicate) -> Q) -> PostfixExpression<Q> {
+        let tokens = self
+            .tokens
+            .into_iter()
+            .map(|token| into_ok(token.try_map(|p| Ok::<_, Infallible>(f(p)))))
+            .collect();
+        PostfixExpression::from_tokens_unchecked(tokens)
+    }
+
+    /// Transforms every predicate of the expression, stopping at the first error.
+    ///
+    /// The returned error carries the index of the predicate that couldn't be transformed.
+    pub fn try_map<Q, E>(
+        self,
+        mut f: impl FnMut(Predicate) -> Result<Q, E>,
+    ) -> Result<PostfixExpression<Q>, PredicateError<E>> {
+        let mut index = 0;
+        let tokens = self
+            .tokens
+            .into_iter()
+            .map(|token| {
+                token.try_map(|p| {
+                    let result = f(p).map_err(|e| PredicateError::new(index, e));
+                    index += 1;
+                    result
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PostfixExpression::from_tokens_unchecked(tokens))
+    }
+
+    #[must_use]
+    pub fn map_ref<Q>(&self, f: impl FnMut(&Predicate) -> Q) -> PostfixExpression<Q> {
+        let tokens = self.tokens.iter().map(PostfixToken::as_ref).collect();
+        PostfixExpression::from_tokens_unchecked(tokens).map(f)
     }
 
     #[must_use]
     pub fn to_infix(self) -> InfixExpression<Predicate> {
-        let mut operator_stack: Vec<Option<Operator>> = Vec::new();
-        let mut output_stack: Vec<VecDeque<InfixToken<Predicate>>> = Vec::new();
+        InfixExpression::from_tokens_unchecked(infix_tokens(self.tokens))
+    }
+
+    #[must_use]
+    pub fn to_prefix(self) -> PrefixExpression<Predicate> {
+        let mut output_stack: Vec<VecDeque<PrefixToken<Predicate>>> = Vec::new();
 
         for token in self.tokens {
             match token {
                 PostfixToken::Predicate(p) => {
-                    output_stack.push(VecDeque::from([InfixToken::Predicate(p)]));
-                    operator_stack.push(None);
+                    output_stack.push(VecDeque::from([PrefixToken::Predicate(p)]));
                 }
                 PostfixToken::Operator(op) => {
-                    let mut p2 = output_stack.remove(output_stack.len() - 1);
-                    let mut p1 = output_stack.remove(output_stack.len() - 1);
-                    let op2 = operator_stack.remove(operator_stack.len() - 1);
-                    let op1 = operator_stack.remove(operator_stack.len() - 1);
-
-                    for (operator, p) in [(op1, &mut p1), (op2, &mut p2)] {
-                        if let Some(operator) = operator {
-                            if operator.precedence() < op.precedence() {
-                                p.push_front(InfixToken::Parenthesis(Parenthesis::Open));
-                                p.push_back(InfixToken::Parenthesis(Parenthesis::Close));
-                            }
-                        }
+                    let mut v = VecDeque::from([PrefixToken::Operator(op)]);
+                    for operand in output_stack.split_off(output_stack.len() - op.arity()) {
+                        v.extend(operand);
                     }
+                    output_stack.push(v);
+                }
+            }
+        }
 
-                    let mut v = VecDeque::new();
-                    v.extend(p1);
-                    v.push_back(InfixToken::Operator(op));
-                    v.extend(p2);
+        PrefixExpression::from_tokens_unchecked(output_stack.remove(0).into())
+    }
 
-                    output_stack.push(v);
-                    operator_stack.push(Some(op));
+    #[must_use]
+    pub fn to_tree(self) -> ExpressionTree<Predicate> {
+        let mut stack: Vec<ExpressionTree<Predicate>> = Vec::new();
+        for token in self.tokens {
+            match token {
+                PostfixToken::Predicate(p) => stack.push(ExpressionTree::Leaf(p)),
+                PostfixToken::Operator(op) => {
+                    let operands = stack.split_off(stack.len() - op.arity());
+                    stack.push(ExpressionTree::from_operator(op, operands));
                 }
             }
         }
+        stack.remove(0)
+    }
 
-        InfixExpression::from_tokens_unchecked(output_stack.remove(0).into())
+    /// Renders the expression in infix notation using the given keyword style,
+    /// with the same minimal parenthesization as [`PostfixExpression::to_infix`].
+    pub fn display(&self, style: KeywordStyle) -> impl fmt::Display + '_
+    where
+        Predicate: fmt::Display,
+    {
+        display_with(move |f| {
+            let tokens = infix_tokens(self.tokens.iter().map(PostfixToken::as_ref));
+            write_infix(f, &tokens, style, |f, p| write!(f, "{p}"))
+        })
     }
 
+    /// Evaluates the expression, resolving predicates through the given evaluator.
+    ///
+    /// Operands are evaluated strictly left to right, and evaluation short-circuits:
+    /// whenever the first operand of `AND`, `OR`, `NAND`, `NOR` or `IMPLIES` already determines
+    /// the result, the whole second operand is skipped and none of its predicates is evaluated.
     pub fn evaluate(&self, evaluator: &dyn PredicateEvaluator<Predicate = Predicate>) -> bool {
-        let mut stack: Vec<PostfixStackItem<Predicate>> = Vec::new();
+        self.evaluate_with(&mut EvalContext::new(), evaluator)
+    }
+
+    /// Evaluates the expression like [`PostfixExpression::evaluate`], using the buffers of
+    /// `context` instead of allocating new ones.
+    pub fn evaluate_with(
+        &self,
+        context: &mut EvalContext,
+        evaluator: &dyn PredicateEvaluator<Predicate = Predicate>,
+    ) -> bool {
+        context.prepare(&self.tokens);
+        into_ok(self.evaluate_in(
+            context,
+            |p| Ok::<_, Infallible>(evaluator.evaluate_predicate(p)),
+            |_, _, _| {},
+        ))
+    }
+
+    /// Evaluates the expression against each of the given evaluators, in order,
+    /// reusing the buffers of `context` for all of them.
+    pub fn evaluate_many<E>(&self, context: &mut EvalContext, evaluators: &[E]) -> Vec<bool>
+    where
+        E: PredicateEvaluator<Predicate = Predicate>,
+    {
+        context.prepare(&self.tokens);
+        evaluators
+            .iter()
+            .map(|evaluator| {
+                into_ok(self.evaluate_in(
+                    context,
+                    |p| Ok::<_, Infallible>(evaluator.evaluate_predicate(p)),
+                    |_, _, _| {},
+                ))
+            })
+            .collect()
+    }
+
+    /// Evaluates the expression against all the given evaluators at once, returning a set
+    /// with bit `i` set when the expression holds for `evaluators[i]`.
+    ///
+    /// Each distinct predicate is first evaluated into a column with
+    /// [`PredicateEvaluator::evaluate_column`], then the columns are combined word by word
+    /// along the postfix order. There is no short-circuiting: every distinct predicate
+    /// is evaluated for every evaluator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column returned by [`PredicateEvaluator::evaluate_column`] doesn't hold
+    /// exactly one bit per evaluator.
+    pub fn evaluate_batch<E>(&self, evaluators: &[E]) -> BitSet
+    where
+        E: PredicateEvaluator<Predicate = Predicate>,
+        Predicate: PartialEq,
+    {
+        let mut distinct: Vec<&Predicate> = Vec::new();
+        let mut columns: Vec<BitSet> = Vec::new();
+        let mut column_of: Vec<usize> = Vec::new();
+        for p in self.predicates() {
+            let column = distinct.iter().position(|d| *d == p).unwrap_or_else(|| {
+                let column = E::evaluate_column(evaluators, p);
+                assert_eq!(
+                    column.len(),
+                    evaluators.len(),
+                    "a column must hold one bit per evaluator"
+                );
+                distinct.push(p);
+                columns.push(column);
+                columns.len() - 1
+            });
+            column_of.push(column);
+        }
+
+        let word_count = evaluators.len().div_ceil(64);
+        let mut predicate_index = 0;
+        let mut stack: Vec<Cow<'_, [u64]>> = Vec::new();
         for token in &self.tokens {
             match token {
-                PostfixToken::Operator(op) => {
-                    let mut p2 = stack.remove(stack.len() - 1);
-                    let mut p1 = stack.remove(stack.len() - 1);
-                    if matches!(p1, PostfixStackItem::Predicate(_))
-                        && matches!(p2, PostfixStackItem::Result(_))
-                    {
-                        std::mem::swap(&mut p1, &mut p2);
-                    }
-                    let result = match op {
-                        Operator::And => p1.evaluate(evaluator) && p2.evaluate(evaluator),
-                        Operator::Or => p1.evaluate(evaluator) || p2.evaluate(evaluator),
-                    };
-                    stack.push(PostfixStackItem::Result(result));
+                PostfixToken::Predicate(_) => {
+                    let column = &columns[column_of[predicate_index]];
+                    stack.push(Cow::Borrowed(column.words()));
+                    predicate_index += 1;
                 }
-                PostfixToken::Predicate(p) => {
-                    stack.push(PostfixStackItem::Predicate(p));
+                PostfixToken::Operator(op) => {
+                    let operands = stack.split_off(stack.len() - op.arity());
+                    let mut words = [0; 2];
+                    let result = (0..word_count)
+                        .map(|w| {
+                            for (word, operand) in words.iter_mut().zip(&operands) {
+                                *word = operand[w];
+                            }
+                            op.apply_words(&words[..op.arity()])
+                        })
+                        .collect();
+                    stack.push(Cow::Owned(result));
                 }
             }
         }
-        stack.remove(stack.len() - 1).evaluate(evaluator)
+
+        BitSet::from_words(stack.remove(0).into_owned(), evaluators.len())
+    }
+
+    /// Evaluates the expression like [`PostfixExpression::evaluate`], but with an evaluator
+    /// that can fail.
+    ///
+    /// The first error returned by the evaluator is propagated,
+    /// and no other predicate is evaluated after it.
+    pub fn try_evaluate<E>(
+        &self,
+        evaluator: &dyn TryPredicateEvaluator<Predicate = Predicate, Error = E>,
+    ) -> Result<bool, E> {
+        let mut context = EvalContext::new();
+        context.prepare(&self.tokens);
+        self.evaluate_in(
+            &mut context,
+            |p| evaluator.try_evaluate_predicate(p),
+            |_, _, _| {},
+        )
+    }
+
+    /// Evaluates the expression like [`PostfixExpression::evaluate`], and also returns a trace
+    /// of every predicate evaluation and operator application, in evaluation order.
+    ///
+    /// The trace displays as the expression in infix notation, with the value of every
+    /// evaluated predicate, such as `A[true] AND (B[false] OR C[true])`.
+    pub fn evaluate_traced(
+        &self,
+        evaluator: &dyn PredicateEvaluator<Predicate = Predicate>,
+    ) -> (bool, Trace<'_, Predicate>) {
+        let mut context = EvalContext::new();
+        context.prepare(&self.tokens);
+        let mut steps = Vec::new();
+        let result = self.evaluate_in(
+            &mut context,
+            |p| Ok::<_, Infallible>(evaluator.evaluate_predicate(p)),
+            |index, operands, value| {
+                steps.push(match self.tokens[index] {
+                    PostfixToken::Predicate(_) => TraceStep::Predicate { index, value },
+                    PostfixToken::Operator(operator) => TraceStep::Operator {
+                        index,
+                        operator,
+                        operands: operands.to_vec(),
+                        value,
+                    },
+                });
+            },
+        );
+        (into_ok(result), Trace::new(self, steps))
+    }
+
+    /// Evaluates the expression like [`PostfixExpression::evaluate`], and also returns
+    /// a minimal set of predicate values that justifies the result, such as `A` being false
+    /// for `A AND (B OR C)`.
+    ///
+    /// Predicates are identified by their index, counting only predicates in token order,
+    /// and are sorted by index. Every predicate occurrence is considered independent:
+    /// the returned values determine the result whatever the values of the other
+    /// predicates, and none of them can be left out.
+    pub fn evaluate_witness(
+        &self,
+        evaluator: &dyn PredicateEvaluator<Predicate = Predicate>,
+    ) -> (bool, Vec<(usize, bool)>) {
+        let mut predicate_indices = Vec::with_capacity(self.tokens.len());
+        let mut count = 0;
+        for token in &self.tokens {
+            predicate_indices.push(count);
+            if let PostfixToken::Predicate(_) = token {
+                count += 1;
+            }
+        }
+
+        let mut context = EvalContext::new();
+        context.prepare(&self.tokens);
+        // the witnesses of the sub-expressions evaluated so far
+        let mut witnesses: Vec<Vec<(usize, bool)>> = Vec::new();
+        let result = self.evaluate_in(
+            &mut context,
+            |p| Ok::<_, Infallible>(evaluator.evaluate_predicate(p)),
+            |index, operands, value| {
+                let PostfixToken::Operator(op) = self.tokens[index] else {
+                    witnesses.push(vec![(predicate_indices[index], value)]);
+                    return;
+                };
+                let operand_witnesses = witnesses.split_off(witnesses.len() - operands.len());
+                // short-circuiting leaves at most one controlling operand
+                let controlling = operand_witnesses
+                    .iter()
+                    .zip(operands)
+                    .enumerate()
+                    .filter(|(position, (_, value))| op.is_controlling(*position, **value))
+                    .map(|(_, (witness, _))| witness)
+                    .next();
+                let witness = match controlling {
+                    Some(witness) => witness.clone(),
+                    None => operand_witnesses.concat(),
+                };
+                witnesses.push(witness);
+            },
+        );
+
+        let mut witness = witnesses.remove(0);
+        witness.sort_unstable();
+        (into_ok(result), witness)
+    }
+
+    /// Evaluates the predicates known to `evaluator`, and simplifies the expression accordingly.
+    ///
+    /// Returns the value of the expression if the known predicates determine it, or else
+    /// the residual expression over the unknown predicates, where known values are folded away:
+    /// `true AND X` becomes `X`, `false OR X` becomes `X`, `true XOR X` becomes `NOT X`, etc.
+    #[must_use]
+    pub fn partially_evaluate(
+        &self,
+        evaluator: &dyn PartialPredicateEvaluator<Predicate = Predicate>,
+    ) -> PartialEvaluation<Predicate>
+    where
+        Predicate: Clone,
+    {
+        // sub-expressions are either known values or residual tokens
+        let mut stack: Vec<Result<bool, Vec<PostfixToken<Predicate>>>> = Vec::new();
+        let negate = |mut tokens: Vec<PostfixToken<Predicate>>| {
+            if let Some(PostfixToken::Operator(Operator::Not)) = tokens.last() {
+                tokens.pop();
+            } else {
+                tokens.push(PostfixToken::Operator(Operator::Not));
+            }
+            tokens
+        };
+
+        for token in &self.tokens {
+            let item = match token {
+                PostfixToken::Predicate(p) => match evaluator.partially_evaluate_predicate(p) {
+                    Some(value) => Ok(value),
+                    None => Err(vec![PostfixToken::Predicate(p.clone())]),
+                },
+                PostfixToken::Operator(op) => {
+                    let mut values = [false; 2];
+                    let mut residuals = Vec::new();
+                    let operands = stack.split_off(stack.len() - op.arity());
+                    for (position, operand) in operands.into_iter().enumerate() {
+                        match operand {
+                            Ok(value) => values[position] = value,
+                            Err(residual) => residuals.push((position, residual)),
+                        }
+                    }
+
+                    match residuals.as_mut_slice() {
+                        [] => Ok(op.apply(&values[..op.arity()])),
+                        [(position, residual)] => {
+                            // the result is a function of the single unknown operand
+                            let residual = mem::take(residual);
+                            let mut result_for = |value| {
+                                values[*position] = value;
+                                op.apply(&values[..op.arity()])
+                            };
+                            match (result_for(false), result_for(true)) {
+                                (when_false, when_true) if when_false == when_true => {
+                                    Ok(when_false)
+                                }
+                                (false, true) => Err(residual),
+                                _ => Err(negate(residual)),
+                            }
+                        }
+                        _ => {
+                            let mut tokens = Vec::new();
+                            for (_, residual) in residuals {
+                                tokens.extend(residual);
+                            }
+                            tokens.push(PostfixToken::Operator(*op));
+                            Err(tokens)
+                        }
+                    }
+                }
+            };
+            stack.push(item);
+        }
+
+        match stack.remove(0) {
+            Ok(value) => PartialEvaluation::Value(value),
+            Err(tokens) => PartialEvaluation::Residual(Self::from_tokens_unchecked(tokens)),
+        }
+    }
+
+    /// Evaluates the expression in Kleene's three-valued logic, where predicates can be
+    /// unknown.
+    ///
+    /// An operator gives an unknown result only when its result would depend on the value
+    /// of an unknown operand: `false AND unknown` is false, but `true AND unknown` is unknown.
+    /// Evaluation short-circuits like [`PostfixExpression::evaluate`] when the first operand
+    /// is known and determines the result.
+    pub fn evaluate_kleene(
+        &self,
+        evaluator: &dyn TriStatePredicateEvaluator<Predicate = Predicate>,
+    ) -> Truth {
+        let parents = operand_parents(&self.tokens);
+        into_ok(self.walk(
+            &Kleene,
+            &parents,
+            &mut Vec::new(),
+            |p| Ok::<_, Infallible>(evaluator.evaluate_tri_state_predicate(p)),
+            |_, _, _| {},
+        ))
+    }
+
+    /// Evaluates the expression in fuzzy logic, combining the scores of the predicates into
+    /// a degree of match between 0 and 1, with the operators of the given family.
+    ///
+    /// Scores outside [0, 1] are clamped, and NaN is treated as 0. `NOT a` is `1 - a`,
+    /// `a IMPLIES b` is `NOT a OR b`, and `a XOR b` is `(a AND NOT b) OR (NOT a AND b)`.
+    /// Evaluation short-circuits like [`PostfixExpression::evaluate`] when the first operand
+    /// is exactly 0 or 1 and determines the result.
+    pub fn evaluate_fuzzy(
+        &self,
+        evaluator: &dyn ScoringEvaluator<Predicate = Predicate>,
+        logic: FuzzyLogic,
+    ) -> f64 {
+        let parents = operand_parents(&self.tokens);
+        into_ok(self.walk(
+            &logic,
+            &parents,
+            &mut Vec::new(),
+            |p| Ok::<_, Infallible>(clamp_score(evaluator.score_predicate(p))),
+            |_, _, _| {},
+        ))
+    }
+
+    /// Returns the probability that the expression is true, when each predicate is true
+    /// independently with the probability given by its score.
+    ///
+    /// Equal predicates are the same event. When every predicate appears once, the
+    /// probabilities are combined in a single pass; otherwise the expression is expanded
+    /// over both values of each repeated predicate (Shannon expansion), which takes time
+    /// exponential in the number of distinct repeated predicates.
+    /// Scores outside [0, 1] are clamped, and NaN is treated as 0.
+    pub fn probability(&self, evaluator: &dyn ScoringEvaluator<Predicate = Predicate>) -> f64
+    where
+        Predicate: Eq + Hash,
+    {
+        let mut variables: HashMap<&Predicate, usize> = HashMap::new();
+        let mut probabilities = Vec::new();
+        let mut occurrences = Vec::new();
+        for p in self.predicates() {
+            let variable = *variables.entry(p).or_insert_with(|| {
+                probabilities.push(clamp_score(evaluator.score_predicate(p)));
+                occurrences.push(0);
+                probabilities.len() - 1
+            });
+            occurrences[variable] += 1;
+        }
+        let repeated: Vec<usize> = (0..occurrences.len())
+            .filter(|&variable| occurrences[variable] > 1)
+            .collect();
+
+        let parents = operand_parents(&self.tokens);
+        self.expand_probability(
+            &parents,
+            &variables,
+            &mut probabilities,
+            &repeated,
+            &mut Vec::new(),
+        )
+    }
+
+    /// Returns the truth table of the expression, with the result for every assignment of
+    /// values to its distinct predicates, or `None` if it has more than
+    /// [`TruthTable::MAX_PREDICATES`] distinct predicates.
+    ///
+    /// Each row is computed with [`PostfixExpression::evaluate`].
+    #[must_use]
+    pub fn truth_table(&self) -> Option<TruthTable<'_, Predicate>>
+    where
+        Predicate: Eq + Hash,
+    {
+        TruthTable::new(self)
     }
 
     pub(crate) fn from_tokens_unchecked(tokens: Vec<PostfixToken<Predicate>>) -> Self {
         Self { tokens }
     }
 
-    fn are_tokens_valid(tokens: &[PostfixToken<Predicate>]) -> bool {
+    /// Evaluates the expression with a context prepared for its tokens.
+    ///
+    /// `observe` is called with the token index, the operands and the value of every
+    /// predicate evaluation and operator application, in evaluation order.
+    fn evaluate_in<E>(
+        &self,
+        context: &mut EvalContext,
+        evaluate_predicate: impl FnMut(&Predicate) -> Result<bool, E>,
+        observe: impl FnMut(usize, &[bool], bool),
+    ) -> Result<bool, E> {
+        let EvalContext { stack, parents, .. } = context;
+        self.walk(&Boolean, parents, stack, evaluate_predicate, observe)
+    }
+
+    /// Returns the probability of the expression, summed over both values of each of the
+    /// `repeated` variables.
+    ///
+    /// Once every repeated variable has a fixed value, the operands of each operator depend
+    /// on disjoint sets of independent variables, so their probabilities can be combined.
+    fn expand_probability(
+        &self,
+        parents: &[Option<OperandParent>],
+        variables: &HashMap<&Predicate, usize>,
+        probabilities: &mut [f64],
+        repeated: &[usize],
+        stack: &mut Vec<f64>,
+    ) -> f64
+    where
+        Predicate: Eq + Hash,
+    {
+        let Some((&variable, repeated)) = repeated.split_first() else {
+            return into_ok(self.walk(
+                &Probability,
+                parents,
+                stack,
+                |p| Ok::<_, Infallible>(probabilities[variables[p]]),
+                |_, _, _| {},
+            ));
+        };
+
+        let probability = probabilities[variable];
+        let mut result = 0.0;
+        for (value, weight) in [(0.0, 1.0 - probability), (1.0, probability)] {
+            if weight > 0.0 {
+                probabilities[variable] = value;
+                result += weight
+                    * self.expand_probability(parents, variables, probabilities, repeated, stack);
+            }
+        }
+        probabilities[variable] = probability;
+        result
+    }
+
+    /// Evaluates the expression left to right with short-circuiting, combining the values
+    /// of predicates according to `logic`.
+    fn walk<L: Logic, E>(
+        &self,
+        logic: &L,
+        parents: &[Option<OperandParent>],
+        stack: &mut Vec<L::Value>,
+        mut evaluate_predicate: impl FnMut(&Predicate) -> Result<L::Value, E>,
+        mut observe: impl FnMut(usize, &[L::Value], L::Value),
+    ) -> Result<L::Value, E> {
+        stack.clear();
+        let mut i = 0;
+
+        while i < self.tokens.len() {
+            let mut result = match &self.tokens[i] {
+                PostfixToken::Predicate(p) => {
+                    let result = evaluate_predicate(p)?;
+                    observe(i, &[], result);
+                    result
+                }
+                PostfixToken::Operator(op) => {
+                    let first = stack.len() - op.arity();
+                    let result = logic.apply(*op, &stack[first..]);
+                    observe(i, &stack[first..], result);
+                    stack.truncate(first);
+                    result
+                }
+            };
+
+            // skip the remaining operands of every operator whose result is already known
+            let mut end = i;
+            while let Some(parent) = parents[end].filter(|parent| parent.is_first_operand) {
+                let PostfixToken::Operator(op) = self.tokens[parent.operator_index] else {
+                    unreachable!("the parent of an operand is always an operator");
+                };
+                let Some(short_circuit) = logic.short_circuit(op, result) else {
+                    break;
+                };
+                observe(parent.operator_index, &[result], short_circuit);
+                result = short_circuit;
+                end = parent.operator_index;
+            }
+
+            stack.push(result);
+            i = end + 1;
+        }
+
+        Ok(stack[0])
+    }
+
+    fn validate_tokens(tokens: &[PostfixToken<Predicate>]) -> Result<(), ValidationError> {
         let mut cnt: usize = 0;
 
-        for token in tokens {
+        for (i, token) in tokens.iter().enumerate() {
             match token {
-                PostfixToken::Operator(_) => {
-                    if cnt < 2 {
-                        return false;
+                PostfixToken::Operator(op) => {
+                    if cnt < op.arity() {
+                        return Err(ValidationError::MissingOperand(i));
                     }
-                    cnt -= 1;
+                    cnt = cnt - op.arity() + 1;
                 }
                 PostfixToken::Predicate(_) => {
                     cnt += 1;
@@ -102,6 +880,119 @@ impl<Predicate> PostfixExpression<Predicate> {
             }
         }
 
-        cnt == 1
+        match cnt {
+            0 => Err(ValidationError::EmptyExpression),
+            1 => Ok(()),
+            _ => Err(ValidationError::MissingOperator(tokens.len())),
+        }
     }
 }
+
+impl<Predicate: fmt::Display> fmt::Display for PostfixExpression<Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(KeywordStyle::default()).fmt(f)
+    }
+}
+
+impl<Predicate> IntoIterator for PostfixExpression<Predicate> {
+    type Item = PostfixToken<Predicate>;
+    type IntoIter = vec::IntoIter<PostfixToken<Predicate>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.into_iter()
+    }
+}
+
+impl<'a, Predicate> IntoIterator for &'a PostfixExpression<Predicate> {
+    type Item = &'a PostfixToken<Predicate>;
+    type IntoIter = slice::Iter<'a, PostfixToken<Predicate>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
+    }
+}
+
+/// Serializes the expression as its sequence of tokens.
+#[cfg(feature = "serde")]
+impl<Predicate: serde::Serialize> serde::Serialize for PostfixExpression<Predicate> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.tokens.serialize(serializer)
+    }
+}
+
+/// Deserializes the expression from its sequence of tokens, rejecting invalid expressions
+/// as [`PostfixExpression::try_from_tokens`] does.
+#[cfg(feature = "serde")]
+impl<'de, Predicate: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for PostfixExpression<Predicate>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tokens = Vec::<PostfixToken<Predicate>>::deserialize(deserializer)?;
+        Self::try_from_tokens(tokens).map_err(serde::de::Error::custom)
+    }
+}
+
+pub(crate) fn infix_tokens<Predicate>(
+    tokens: impl IntoIterator<Item = PostfixToken<Predicate>>,
+) -> Vec<InfixToken<Predicate>> {
+    // the outermost operator of each operand, and whether it's the only operator of its
+    // precedence level written outside of parentheses
+    let mut operator_stack: Vec<Option<(Operator, bool)>> = Vec::new();
+    let mut output_stack: Vec<VecDeque<InfixToken<Predicate>>> = Vec::new();
+
+    for token in tokens {
+        match token {
+            PostfixToken::Predicate(p) => {
+                output_stack.push(VecDeque::from([InfixToken::Predicate(p)]));
+                operator_stack.push(None);
+            }
+            PostfixToken::Operator(op) if op.is_unary() => {
+                let mut p = output_stack.remove(output_stack.len() - 1);
+                if let Some((operator, _)) = operator_stack.remove(operator_stack.len() - 1) {
+                    if op.needs_parenthesis(operator, true) {
+                        p.push_front(InfixToken::Parenthesis(Parenthesis::Open));
+                        p.push_back(InfixToken::Parenthesis(Parenthesis::Close));
+                    }
+                }
+                p.push_front(InfixToken::Operator(op));
+
+                output_stack.push(p);
+                operator_stack.push(Some((op, true)));
+            }
+            PostfixToken::Operator(op) => {
+                let mut p2 = output_stack.remove(output_stack.len() - 1);
+                let mut p1 = output_stack.remove(output_stack.len() - 1);
+                let op2 = operator_stack.remove(operator_stack.len() - 1);
+                let op1 = operator_stack.remove(operator_stack.len() - 1);
+                let mut is_alone = true;
+
+                for (operator, p, is_right) in [(op1, &mut p1, false), (op2, &mut p2, true)] {
+                    let Some((operator, operator_is_alone)) = operator else {
+                        continue;
+                    };
+                    let same_precedence = operator.precedence() == op.precedence();
+                    // `a AND (b NAND c AND d)` must keep its parentheses even though `AND` is
+                    // associative, as `b NAND c` would otherwise be regrouped with `a`
+                    if op.needs_parenthesis(operator, is_right)
+                        || (is_right && same_precedence && !operator_is_alone)
+                    {
+                        p.push_front(InfixToken::Parenthesis(Parenthesis::Open));
+                        p.push_back(InfixToken::Parenthesis(Parenthesis::Close));
+                    } else if same_precedence {
+                        is_alone &= operator == op && operator_is_alone;
+                    }
+                }
+
+                let mut v = VecDeque::new();
+                v.extend(p1);
+                v.push_back(InfixToken::Operator(op));
+                v.extend(p2);
+
+                output_stack.push(v);
+                operator_stack.push(Some((op, is_alone)));
+            }
+        }
+    }
+
+    output_stack.remove(0).into()
+}
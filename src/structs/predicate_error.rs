@@ -0,0 +1,44 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error raised while processing a predicate, together with the index of that predicate
+/// (counting only predicates, in token order).
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PredicateError<E> {
+    index: usize,
+    error: E,
+}
+
+impl<E> PredicateError<E> {
+    pub(crate) fn new(index: usize, error: E) -> Self {
+        Self { index, error }
+    }
+
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[must_use]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    #[must_use]
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for PredicateError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "predicate {}: {}", self.index, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for PredicateError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
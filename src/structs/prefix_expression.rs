@@ -0,0 +1,275 @@
+use crate::enums::prefix_token::PrefixToken;
+use crate::internals::display_with::display_with;
+use crate::internals::infix_writer::write_infix;
+use crate::internals::into_ok::into_ok;
+use crate::structs::postfix_expression::infix_tokens;
+use crate::structs::predicate_error::PredicateError;
+use crate::traits::predicate_evaluator::PredicateEvaluator;
+use crate::{
+    InfixExpression, KeywordStyle, Operator, PostfixExpression, PostfixToken, ValidationError,
+};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::fmt;
+use std::{slice, vec};
+
+#[derive(Debug, PartialEq)]
+pub struct PrefixExpression<Predicate> {
+    tokens: Vec<PrefixToken<Predicate>>,
+}
+
+impl<Predicate> PrefixExpression<Predicate> {
+    #[must_use]
+    pub fn from_tokens(tokens: Vec<PrefixToken<Predicate>>) -> Option<Self> {
+        Self::try_from_tokens(tokens).ok()
+    }
+
+    pub fn try_from_tokens(tokens: Vec<PrefixToken<Predicate>>) -> Result<Self, ValidationError> {
+        Self::validate_tokens(&tokens).map(|()| Self { tokens })
+    }
+
+    #[must_use]
+    pub fn tokens(&self) -> &[PrefixToken<Predicate>] {
+        &self.tokens
+    }
+
+    #[must_use]
+    pub fn into_tokens(self) -> Vec<PrefixToken<Predicate>> {
+        self.tokens
+    }
+
+    /// Returns the number of tokens in the expression.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, PrefixToken<Predicate>> {
+        self.tokens.iter()
+    }
+
+    /// Iterates over the predicates of the expression, in token order.
+    pub fn predicates(&self) -> impl Iterator<Item = &Predicate> {
+        self.tokens.iter().filter_map(|token| match token {
+            PrefixToken::Predicate(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn map<Q>(self, mut f: impl FnMut(Predicate) -> Q) -> PrefixExpression<Q> {
+        let tokens = self
+            .tokens
+            .into_iter()
+            .map(|token| into_ok(token.try_map(|p| Ok::<_, Infallible>(f(p)))))
+            .collect();
+        PrefixExpression::from_tokens_unchecked(tokens)
+    }
+
+    /// Transforms every predicate of the expression, stopping at the first error.
+    ///
+    /// The returned error carries the index of the predicate that couldn't be transformed.
+    pub fn try_map<Q, E>(
+        self,
+        mut f: impl FnMut(Predicate) -> Result<Q, E>,
+    ) -> Result<PrefixExpression<Q>, PredicateError<E>> {
+        let mut index = 0;
+        let tokens = self
+            .tokens
+            .into_iter()
+            .map(|token| {
+                token.try_map(|p| {
+                    let result = f(p).map_err(|e| PredicateError::new(index, e));
+                    index += 1;
+                    result
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PrefixExpression::from_tokens_unchecked(tokens))
+    }
+
+    #[must_use]
+    pub fn map_ref<Q>(&self, f: impl FnMut(&Predicate) -> Q) -> PrefixExpression<Q> {
+        let tokens = self.tokens.iter().map(PrefixToken::as_ref).collect();
+        PrefixExpression::from_tokens_unchecked(tokens).map(f)
+    }
+
+    #[must_use]
+    pub fn to_postfix(self) -> PostfixExpression<Predicate> {
+        PostfixExpression::from_tokens_unchecked(postfix_tokens(self.tokens.into_iter()))
+    }
+
+    #[must_use]
+    pub fn to_infix(self) -> InfixExpression<Predicate> {
+        self.to_postfix().to_infix()
+    }
+
+    /// Renders the expression in infix notation using the given keyword style,
+    /// with the same minimal parenthesization as [`PrefixExpression::to_infix`].
+    pub fn display(&self, style: KeywordStyle) -> impl fmt::Display + '_
+    where
+        Predicate: fmt::Display,
+    {
+        display_with(move |f| {
+            let tokens = infix_tokens(postfix_tokens(self.tokens.iter().map(PrefixToken::as_ref)));
+            write_infix(f, &tokens, style, |f, p| write!(f, "{p}"))
+        })
+    }
+
+    /// Evaluates the expression, resolving predicates through the given evaluator.
+    ///
+    /// Like [`PostfixExpression::evaluate`], operands are evaluated left to right
+    /// and the second operand of an operator is skipped when the first one already
+    /// determines the result.
+    pub fn evaluate(&self, evaluator: &dyn PredicateEvaluator<Predicate = Predicate>) -> bool {
+        let mut pending: Vec<(Operator, Option<bool>)> = Vec::new();
+        let mut i = 0;
+
+        loop {
+            let mut result = match &self.tokens[i] {
+                PrefixToken::Operator(op) => {
+                    pending.push((*op, None));
+                    i += 1;
+                    continue;
+                }
+                PrefixToken::Predicate(p) => evaluator.evaluate_predicate(p),
+            };
+            i += 1;
+
+            loop {
+                let Some((op, first_operand)) = pending.last_mut() else {
+                    return result;
+                };
+                match first_operand {
+                    _ if op.is_unary() => result = op.apply(&[result]),
+                    Some(first_operand) => result = op.apply(&[*first_operand, result]),
+                    None => match op.short_circuit(result) {
+                        Some(short_circuit) => {
+                            result = short_circuit;
+                            i = self.skip_operand(i);
+                        }
+                        None => {
+                            *first_operand = Some(result);
+                            break;
+                        }
+                    },
+                }
+                pending.pop();
+            }
+        }
+    }
+
+    pub(crate) fn from_tokens_unchecked(tokens: Vec<PrefixToken<Predicate>>) -> Self {
+        Self { tokens }
+    }
+
+    /// Returns the index of the token following the operand starting at `start`.
+    fn skip_operand(&self, start: usize) -> usize {
+        let mut missing: usize = 1;
+        let mut i = start;
+        while missing > 0 {
+            match &self.tokens[i] {
+                PrefixToken::Operator(op) => missing += op.arity() - 1,
+                PrefixToken::Predicate(_) => missing -= 1,
+            }
+            i += 1;
+        }
+        i
+    }
+
+    fn validate_tokens(tokens: &[PrefixToken<Predicate>]) -> Result<(), ValidationError> {
+        // number of operands still needed to complete the expression
+        let mut missing: usize = 1;
+
+        for (i, token) in tokens.iter().enumerate() {
+            if missing == 0 {
+                return Err(ValidationError::MissingOperator(i));
+            }
+            match token {
+                PrefixToken::Operator(op) => missing += op.arity() - 1,
+                PrefixToken::Predicate(_) => missing -= 1,
+            }
+        }
+
+        match missing {
+            0 => Ok(()),
+            _ if tokens.is_empty() => Err(ValidationError::EmptyExpression),
+            _ => Err(ValidationError::MissingOperand(tokens.len())),
+        }
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for PrefixExpression<Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(KeywordStyle::default()).fmt(f)
+    }
+}
+
+impl<Predicate> IntoIterator for PrefixExpression<Predicate> {
+    type Item = PrefixToken<Predicate>;
+    type IntoIter = vec::IntoIter<PrefixToken<Predicate>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.into_iter()
+    }
+}
+
+impl<'a, Predicate> IntoIterator for &'a PrefixExpression<Predicate> {
+    type Item = &'a PrefixToken<Predicate>;
+    type IntoIter = slice::Iter<'a, PrefixToken<Predicate>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
+    }
+}
+
+/// Serializes the expression as its sequence of tokens.
+#[cfg(feature = "serde")]
+impl<Predicate: serde::Serialize> serde::Serialize for PrefixExpression<Predicate> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.tokens.serialize(serializer)
+    }
+}
+
+/// Deserializes the expression from its sequence of tokens, rejecting invalid expressions
+/// as [`PrefixExpression::try_from_tokens`] does.
+#[cfg(feature = "serde")]
+impl<'de, Predicate: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for PrefixExpression<Predicate>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tokens = Vec::<PrefixToken<Predicate>>::deserialize(deserializer)?;
+        Self::try_from_tokens(tokens).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Reorders prefix tokens into postfix order.
+fn postfix_tokens<Predicate>(
+    tokens: impl DoubleEndedIterator<Item = PrefixToken<Predicate>>,
+) -> Vec<PostfixToken<Predicate>> {
+    let mut output_stack: Vec<VecDeque<PostfixToken<Predicate>>> = Vec::new();
+
+    for token in tokens.rev() {
+        match token {
+            PrefixToken::Predicate(p) => {
+                output_stack.push(VecDeque::from([PostfixToken::Predicate(p)]));
+            }
+            PrefixToken::Operator(op) => {
+                // the first operand is on top of the stack
+                let mut v = VecDeque::new();
+                for _ in 0..op.arity() {
+                    v.extend(output_stack.remove(output_stack.len() - 1));
+                }
+                v.push_back(PostfixToken::Operator(op));
+                output_stack.push(v);
+            }
+        }
+    }
+
+    output_stack.remove(0).into()
+}
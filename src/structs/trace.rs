@@ -0,0 +1,64 @@
+use crate::internals::display_with::display_with;
+use crate::internals::infix_writer::write_infix;
+use crate::structs::postfix_expression::infix_tokens;
+use crate::{KeywordStyle, PostfixExpression, PostfixToken, TraceStep};
+use std::fmt;
+
+/// The steps of an evaluation of a postfix expression, in evaluation order.
+#[derive(Debug)]
+pub struct Trace<'a, Predicate> {
+    expression: &'a PostfixExpression<Predicate>,
+    steps: Vec<TraceStep>,
+}
+
+impl<'a, Predicate> Trace<'a, Predicate> {
+    pub(crate) fn new(expression: &'a PostfixExpression<Predicate>, steps: Vec<TraceStep>) -> Self {
+        Self { expression, steps }
+    }
+
+    #[must_use]
+    pub fn expression(&self) -> &'a PostfixExpression<Predicate> {
+        self.expression
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    /// Renders the expression in infix notation using the given keyword style,
+    /// annotating every evaluated predicate with its value, such as `A[true] AND B[false]`.
+    pub fn display(&self, style: KeywordStyle) -> impl fmt::Display + '_
+    where
+        Predicate: fmt::Display,
+    {
+        display_with(move |f| {
+            let mut values = vec![None; self.expression.len()];
+            for step in &self.steps {
+                if let TraceStep::Predicate { index, value } = step {
+                    values[*index] = Some(*value);
+                }
+            }
+            let tokens =
+                infix_tokens(
+                    self.expression
+                        .iter()
+                        .enumerate()
+                        .map(|(i, token)| match token {
+                            PostfixToken::Predicate(p) => PostfixToken::Predicate((i, p)),
+                            PostfixToken::Operator(op) => PostfixToken::Operator(*op),
+                        }),
+                );
+            write_infix(f, &tokens, style, |f, (i, p)| match values[*i] {
+                Some(value) => write!(f, "{p}[{value}]"),
+                None => write!(f, "{p}"),
+            })
+        })
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for Trace<'_, Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(KeywordStyle::default()).fmt(f)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::internals::display_with::display_with;
+use crate::traits::predicate_evaluator::PredicateEvaluator;
+use crate::{BitSet, EvalContext, PostfixExpression, TableStyle};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// The result of an expression for every assignment of values to its distinct predicates.
+///
+/// Rows are in binary counting order, with the first predicate as the most significant bit:
+/// the first row has every predicate false, and the last one has every predicate true.
+#[derive(Debug)]
+pub struct TruthTable<'a, Predicate> {
+    predicates: Vec<&'a Predicate>,
+    results: BitSet,
+}
+
+/// Evaluates the predicates of an expression to the values of one row of a truth table.
+struct Assignment<'a, 'b, Predicate> {
+    variables: &'b HashMap<&'a Predicate, usize>,
+    count: usize,
+    row: usize,
+}
+
+impl<Predicate: Eq + Hash> PredicateEvaluator for Assignment<'_, '_, Predicate> {
+    type Predicate = Predicate;
+
+    fn evaluate_predicate(&self, predicate: &Predicate) -> bool {
+        let variable = self.variables[predicate];
+        self.row & (1 << (self.count - 1 - variable)) != 0
+    }
+}
+
+impl<'a, Predicate> TruthTable<'a, Predicate> {
+    /// The largest number of distinct predicates a truth table can have.
+    pub const MAX_PREDICATES: usize = 16;
+
+    pub(crate) fn new(expression: &'a PostfixExpression<Predicate>) -> Option<Self>
+    where
+        Predicate: Eq + Hash,
+    {
+        let mut variables = HashMap::new();
+        let mut predicates = Vec::new();
+        for p in expression.predicates() {
+            variables.entry(p).or_insert_with(|| {
+                predicates.push(p);
+                predicates.len() - 1
+            });
+        }
+        if predicates.len() > Self::MAX_PREDICATES {
+            return None;
+        }
+
+        let mut context = EvalContext::new();
+        let results = (0..1 << predicates.len())
+            .map(|row| {
+                let assignment = Assignment {
+                    variables: &variables,
+                    count: predicates.len(),
+                    row,
+                };
+                expression.evaluate_with(&mut context, &assignment)
+            })
+            .collect();
+        Some(Self {
+            predicates,
+            results,
+        })
+    }
+
+    /// Returns the distinct predicates of the expression, in order of first appearance.
+    #[must_use]
+    pub fn predicates(&self) -> &[&'a Predicate] {
+        &self.predicates
+    }
+
+    /// Returns the result of the expression for each row.
+    #[must_use]
+    pub fn results(&self) -> &BitSet {
+        &self.results
+    }
+
+    /// Returns the values of the predicates in the given row, in the order of
+    /// [`TruthTable::predicates`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    #[must_use]
+    pub fn assignment(&self, row: usize) -> Vec<bool> {
+        assert!(row < self.results.len(), "row {row} out of bounds");
+        let count = self.predicates.len();
+        (0..count)
+            .map(|variable| row & (1 << (count - 1 - variable)) != 0)
+            .collect()
+    }
+
+    /// Iterates over the rows of the table, as the values of the predicates with the result.
+    pub fn rows(&self) -> impl Iterator<Item = (Vec<bool>, bool)> + '_ {
+        self.results
+            .iter()
+            .enumerate()
+            .map(|(row, result)| (self.assignment(row), result))
+    }
+
+    /// Renders the table in the given style, with a column per predicate followed by a
+    /// `Result` column, and values written `T` or `F`.
+    /// In Markdown, `|` in predicate names is escaped as `\|`.
+    pub fn display(&self, style: TableStyle) -> impl fmt::Display + '_
+    where
+        Predicate: fmt::Display,
+    {
+        display_with(move |f| {
+            let mut headers: Vec<String> = self.predicates.iter().map(|p| p.to_string()).collect();
+            headers.push("Result".to_string());
+            if style == TableStyle::Markdown {
+                // an unescaped pipe would split the cell
+                for header in &mut headers {
+                    *header = header.replace('|', "\\|");
+                }
+            }
+            let widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+
+            let write_row = |f: &mut fmt::Formatter<'_>, cells: &mut dyn Iterator<Item = &str>| {
+                for (cell, width) in cells.zip(&widths) {
+                    write!(f, "| {cell:width$} ")?;
+                }
+                writeln!(f, "|")
+            };
+            let write_rule = |f: &mut fmt::Formatter<'_>, corner: char| {
+                for width in &widths {
+                    write!(f, "{corner}{}", "-".repeat(width + 2))?;
+                }
+                writeln!(f, "{corner}")
+            };
+
+            if style == TableStyle::Ascii {
+                write_rule(f, '+')?;
+            }
+            write_row(f, &mut headers.iter().map(String::as_str))?;
+            write_rule(f, if style == TableStyle::Ascii { '+' } else { '|' })?;
+            for (assignment, result) in self.rows() {
+                let mut cells =
+                    assignment
+                        .into_iter()
+                        .chain([result])
+                        .map(|value| if value { "T" } else { "F" });
+                write_row(f, &mut cells)?;
+            }
+            if style == TableStyle::Ascii {
+                write_rule(f, '+')?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<Predicate: fmt::Display> fmt::Display for TruthTable<'_, Predicate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(TableStyle::default()).fmt(f)
+    }
+}
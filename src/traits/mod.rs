@@ -1 +1,6 @@
+pub mod partial_predicate_evaluator;
+pub mod predicate_encoder;
 pub mod predicate_evaluator;
+pub mod scoring_evaluator;
+pub mod tri_state_predicate_evaluator;
+pub mod try_predicate_evaluator;
@@ -0,0 +1,6 @@
+pub trait PartialPredicateEvaluator {
+    type Predicate;
+
+    /// Returns the value of the predicate, or `None` if it's not known yet.
+    fn partially_evaluate_predicate(&self, predicate: &Self::Predicate) -> Option<bool>;
+}
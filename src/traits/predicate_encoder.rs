@@ -0,0 +1,11 @@
+/// Converts predicates to and from bytes, for the binary encoding of expressions.
+pub trait PredicateEncoder {
+    type Predicate;
+
+    /// Appends the bytes representing `predicate` to `buffer`.
+    fn encode_predicate(&self, predicate: &Self::Predicate, buffer: &mut Vec<u8>);
+
+    /// Reads back a predicate from exactly the bytes written by
+    /// [`PredicateEncoder::encode_predicate`], returning `None` if they are invalid.
+    fn decode_predicate(&self, bytes: &[u8]) -> Option<Self::Predicate>;
+}
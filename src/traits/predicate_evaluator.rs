@@ -1,7 +1,24 @@
+use crate::BitSet;
+
 pub trait PredicateEvaluator {
     type Predicate;
 
     fn evaluate_predicate(&self, predicate: &Self::Predicate) -> bool;
+
+    /// Evaluates one predicate against each of the given evaluators, returning bit `i` set
+    /// when it holds for `evaluators[i]`.
+    ///
+    /// This is used by [`PostfixExpression::evaluate_batch`](crate::PostfixExpression::evaluate_batch),
+    /// and can be overridden by evaluators able to produce a whole column at once.
+    /// The returned set must hold exactly `evaluators.len()` bits.
+    fn evaluate_column(evaluators: &[Self], predicate: &Self::Predicate) -> BitSet
+    where
+        Self: Sized,
+    {
+        BitSet::from_fn(evaluators.len(), |i| {
+            evaluators[i].evaluate_predicate(predicate)
+        })
+    }
 }
 
 impl PredicateEvaluator for () {
@@ -0,0 +1,6 @@
+pub trait ScoringEvaluator {
+    type Predicate;
+
+    /// Returns the degree to which the predicate holds, from 0 (false) to 1 (true).
+    fn score_predicate(&self, predicate: &Self::Predicate) -> f64;
+}
@@ -0,0 +1,8 @@
+use crate::Truth;
+
+pub trait TriStatePredicateEvaluator {
+    type Predicate;
+
+    /// Returns the value of the predicate, which may be [`Truth::Unknown`].
+    fn evaluate_tri_state_predicate(&self, predicate: &Self::Predicate) -> Truth;
+}
@@ -0,0 +1,6 @@
+pub trait TryPredicateEvaluator {
+    type Predicate;
+    type Error;
+
+    fn try_evaluate_predicate(&self, predicate: &Self::Predicate) -> Result<bool, Self::Error>;
+}
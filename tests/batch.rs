@@ -0,0 +1,133 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{BitSet, PostfixExpression, PredicateEvaluator};
+use std::cell::Cell;
+
+/// A record holding a number, matched against predicates on its bits.
+struct Record(u32);
+
+impl PredicateEvaluator for Record {
+    type Predicate = u32;
+
+    fn evaluate_predicate(&self, bit: &u32) -> bool {
+        self.0 & (1 << bit) != 0
+    }
+}
+
+#[test]
+fn test_bit_set() {
+    let mut bits = BitSet::new(130);
+    assert_eq!(bits.len(), 130);
+    assert_eq!(bits.count_ones(), 0);
+    bits.set(0, true);
+    bits.set(64, true);
+    bits.set(129, true);
+    bits.set(3, true);
+    bits.set(3, false);
+    assert!(bits.get(64));
+    assert!(!bits.get(3));
+    assert_eq!(bits.count_ones(), 3);
+    assert_eq!(bits.ones().collect::<Vec<_>>(), vec![0, 64, 129]);
+    assert_eq!(bits.words(), &[1, 1, 2]);
+
+    let collected: BitSet = bits.iter().collect();
+    assert_eq!(collected, bits);
+    assert_eq!(BitSet::from_fn(130, |i| [0, 64, 129].contains(&i)), bits);
+    assert!(BitSet::new(0).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_bit_set_out_of_bounds() {
+    let _ = BitSet::new(64).get(64);
+}
+
+#[test]
+fn test_postfix_evaluate_batch() {
+    let records: Vec<Record> = (0..200).map(Record).collect();
+    for infix in [
+        "0",
+        "NOT 1",
+        "0 AND 1 OR 2",
+        "0 AND (1 OR NOT 2) AND 0",
+        "(0 XOR 3) IFF (1 NAND 2)",
+        "0 IMPLIES 5 NOR 7",
+        "6 AND 7 OR 6 AND NOT 7",
+    ] {
+        let postfix = parse(infix);
+        let bits = postfix.evaluate_batch(&records);
+        assert_eq!(bits.len(), records.len());
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(bits.get(i), postfix.evaluate(record), "{infix} on {i}");
+        }
+        assert_eq!(
+            bits.count_ones(),
+            records.iter().filter(|r| postfix.evaluate(*r)).count()
+        );
+    }
+    assert!(parse("0 OR NOT 0").evaluate_batch::<Record>(&[]).is_empty());
+}
+
+thread_local! {
+    static COLUMNS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Records stored column-wise, with one whole column per predicate.
+struct Columnar;
+
+impl PredicateEvaluator for Columnar {
+    type Predicate = &'static [bool];
+
+    fn evaluate_predicate(&self, _: &Self::Predicate) -> bool {
+        unreachable!("columns are evaluated at once")
+    }
+
+    fn evaluate_column(evaluators: &[Self], column: &Self::Predicate) -> BitSet {
+        COLUMNS.with(|count| count.set(count.get() + 1));
+        column[..evaluators.len()].iter().copied().collect()
+    }
+}
+
+#[test]
+fn test_postfix_evaluate_batch_columns() {
+    const A: &[bool] = &[true, true, false, false];
+    const B: &[bool] = &[true, false, true, false];
+    let postfix = PostfixExpression::parse("a b AND a NOT b NOT AND OR", |s| match s {
+        "a" => Some(A),
+        "b" => Some(B),
+        _ => None,
+    })
+    .unwrap();
+
+    let bits = postfix.evaluate_batch(&[Columnar, Columnar, Columnar, Columnar]);
+    assert_eq!(
+        bits.iter().collect::<Vec<_>>(),
+        vec![true, false, false, true]
+    );
+    // each distinct predicate is evaluated once
+    assert_eq!(COLUMNS.with(Cell::get), 2);
+}
+
+/// Returns whole columns, whatever the number of evaluators.
+struct Unsliced;
+
+impl PredicateEvaluator for Unsliced {
+    type Predicate = &'static [bool];
+
+    fn evaluate_predicate(&self, _: &Self::Predicate) -> bool {
+        unreachable!("columns are evaluated at once")
+    }
+
+    fn evaluate_column(_: &[Self], column: &Self::Predicate) -> BitSet {
+        column.iter().copied().collect()
+    }
+}
+
+#[test]
+#[should_panic(expected = "a column must hold one bit per evaluator")]
+fn test_postfix_evaluate_batch_wrong_column_length() {
+    const A: &[bool] = &[true, true, false, false];
+    let postfix = PostfixExpression::parse("a", |_| Some(A)).unwrap();
+    let _ = postfix.evaluate_batch(&[Unsliced, Unsliced]);
+}
@@ -0,0 +1,174 @@
+use rpn_predicate_interpreter::{
+    DecodeError, Operator, PostfixExpression, PostfixToken, PredicateEncoder, ValidationError,
+};
+
+struct Utf8;
+
+impl PredicateEncoder for Utf8 {
+    type Predicate = String;
+
+    fn encode_predicate(&self, predicate: &String, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(predicate.as_bytes());
+    }
+
+    fn decode_predicate(&self, bytes: &[u8]) -> Option<String> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+fn parse(rpn: &str) -> PostfixExpression<String> {
+    PostfixExpression::parse(rpn, |s| Some(s.to_string())).unwrap()
+}
+
+/// Builds an encoded expression around the given payload, with a valid checksum.
+fn with_header(payload: &[u8]) -> Vec<u8> {
+    let mut bytes = b"RPNE\x01".to_vec();
+    bytes.push(u8::try_from(payload.len()).unwrap());
+    bytes.extend_from_slice(payload);
+    with_checksum(bytes)
+}
+
+fn with_checksum(mut bytes: Vec<u8>) -> Vec<u8> {
+    let mut crc = !0u32;
+    for byte in &bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xEDB8_8320
+            };
+        }
+    }
+    bytes.extend_from_slice(&(!crc).to_le_bytes());
+    bytes
+}
+
+#[test]
+fn test_binary_layout() {
+    let bytes = parse("a bc OR NOT").encode(&Utf8);
+    assert_eq!(bytes, with_header(&[0, 1, b'a', 0, 2, b'b', b'c', 2, 3]));
+}
+
+#[test]
+fn test_binary_round_trip() {
+    for rpn in [
+        "a",
+        "a b c OR AND",
+        "a NOT b NAND c d NOR XOR e IMPLIES f IFF",
+        r#""" "long predicate with spaces and a quote \" in it" AND"#,
+    ] {
+        let postfix = parse(rpn);
+        assert_eq!(
+            PostfixExpression::decode(&postfix.encode(&Utf8), &Utf8),
+            Ok(postfix)
+        );
+    }
+
+    let long = "x".repeat(1000);
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate(long.clone()),
+        PostfixToken::Predicate(long),
+        PostfixToken::Operator(Operator::Or),
+    ])
+    .unwrap();
+    assert_eq!(
+        PostfixExpression::decode(&postfix.encode(&Utf8), &Utf8),
+        Ok(postfix)
+    );
+}
+
+#[test]
+fn test_binary_truncated() {
+    let bytes = parse("a b c OR AND").encode(&Utf8);
+    for len in 0..bytes.len() {
+        // anything long enough to hold a checksum fails the checksum first
+        let error = if len < 9 {
+            DecodeError::Truncated
+        } else {
+            DecodeError::ChecksumMismatch
+        };
+        assert_eq!(
+            PostfixExpression::decode(&bytes[..len], &Utf8),
+            Err(error),
+            "{len}"
+        );
+    }
+
+    let mut bytes = bytes;
+    bytes.push(0);
+    assert_eq!(
+        PostfixExpression::decode(&bytes, &Utf8),
+        Err(DecodeError::ChecksumMismatch)
+    );
+
+    for (bytes, error) in [
+        (b"RPNE\x01".to_vec(), DecodeError::Truncated),
+        (b"RPNE\x01\x05\x00\x01a".to_vec(), DecodeError::Truncated),
+        (
+            b"RPNE\x01\x01\x00\x01a".to_vec(),
+            DecodeError::TrailingBytes,
+        ),
+    ] {
+        assert_eq!(
+            PostfixExpression::decode(&with_checksum(bytes), &Utf8),
+            Err(error)
+        );
+    }
+}
+
+#[test]
+fn test_binary_corrupted() {
+    let bytes = parse("a b c OR AND").encode(&Utf8);
+
+    let mut corrupted = bytes.clone();
+    corrupted[0] = b'X';
+    assert_eq!(
+        PostfixExpression::decode(&corrupted, &Utf8),
+        Err(DecodeError::InvalidHeader)
+    );
+
+    let mut corrupted = bytes.clone();
+    corrupted[4] = 2;
+    assert_eq!(
+        PostfixExpression::decode(&corrupted, &Utf8),
+        Err(DecodeError::UnsupportedVersion(2))
+    );
+
+    for i in 5..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[i] ^= 0x10;
+        assert_eq!(
+            PostfixExpression::decode(&corrupted, &Utf8),
+            Err(DecodeError::ChecksumMismatch),
+            "{i}"
+        );
+    }
+}
+
+#[test]
+fn test_binary_invalid_payload() {
+    let cases = [
+        (vec![0, 1, b'a', 9], DecodeError::InvalidOpcode(9)),
+        (vec![0, 5, b'a'], DecodeError::MalformedPayload),
+        (
+            vec![0, 1, b'a', 0, 1, 0xFF, 1],
+            DecodeError::InvalidPredicate(1),
+        ),
+        (
+            vec![0, 1, b'a', 0, 1, b'b'],
+            DecodeError::InvalidExpression(ValidationError::MissingOperator(2)),
+        ),
+        (
+            vec![],
+            DecodeError::InvalidExpression(ValidationError::EmptyExpression),
+        ),
+    ];
+
+    for (payload, error) in cases {
+        assert_eq!(
+            PostfixExpression::decode(&with_header(&payload), &Utf8),
+            Err(error)
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use rpn_predicate_interpreter::{InfixExpression, PostfixExpression};
+use std::str::FromStr;
+
+/// Parses an infix expression into a postfix expression, reading each predicate with `FromStr`.
+pub fn parse<Predicate: FromStr>(infix: &str) -> PostfixExpression<Predicate> {
+    InfixExpression::parse(infix, |s| s.parse().ok())
+        .unwrap()
+        .to_postfix()
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{
+    CompiledExpression, Operator, PostfixExpression, PostfixToken, PredicateEvaluator,
+    TryPredicateEvaluator,
+};
+use std::cell::RefCell;
+
+/// Assigns values to the predicates `a` to `f` from the bits of a number,
+/// and records the order in which predicates are evaluated.
+struct Assignment {
+    bits: u32,
+    calls: RefCell<Vec<char>>,
+}
+
+impl Assignment {
+    fn new(bits: u32) -> Self {
+        Self {
+            bits,
+            calls: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl PredicateEvaluator for Assignment {
+    type Predicate = char;
+
+    fn evaluate_predicate(&self, predicate: &char) -> bool {
+        self.calls.borrow_mut().push(*predicate);
+        self.bits & (1 << (*predicate as u32 - 'a' as u32)) != 0
+    }
+}
+
+#[test]
+fn test_compiled_matches_postfix_evaluation() {
+    for infix in [
+        "a",
+        "NOT a",
+        "a AND b",
+        "a AND b AND c OR d",
+        "a OR b AND c OR NOT d",
+        "(a OR b) AND (c OR d) AND NOT (e AND f)",
+        "a NAND (b NOR c) NAND d",
+        "a IMPLIES b IMPLIES c AND d",
+        "a XOR (b AND c) XOR (d OR e)",
+        "(a IFF b OR c) IFF NOT (d IMPLIES e XOR f)",
+        "NOT (a AND (b OR (c AND (d OR (e AND f)))))",
+    ] {
+        let postfix = parse(infix);
+        let compiled = parse(infix).compile();
+        for bits in 0..64 {
+            let expected = Assignment::new(bits);
+            let actual = Assignment::new(bits);
+            assert_eq!(
+                compiled.evaluate(&actual),
+                postfix.evaluate(&expected),
+                "{infix} with {bits:06b}"
+            );
+            assert_eq!(actual.calls, expected.calls, "{infix} with {bits:06b}");
+        }
+    }
+}
+
+#[test]
+fn test_compiled_short_circuit() {
+    for (infix, bits, calls) in [
+        ("a AND b AND c AND d", 0b0000, "a"),
+        ("a AND b AND c AND d", 0b0011, "abc"),
+        ("(a OR b) AND (c OR d)", 0b0101, "ac"),
+        ("(a OR b) AND (c OR d)", 0b0000, "ab"),
+        ("a IMPLIES b AND c", 0b0000, "a"),
+        ("a XOR b AND c", 0b0001, "ab"),
+    ] {
+        let compiled = parse(infix).compile();
+        let assignment = Assignment::new(bits);
+        compiled.evaluate(&assignment);
+        assert_eq!(
+            assignment
+                .calls
+                .into_inner()
+                .into_iter()
+                .collect::<String>(),
+            calls,
+            "{infix}"
+        );
+    }
+}
+
+#[test]
+fn test_compiled_deeply_nested() {
+    // x XOR (x XOR (... XOR x)), nested deeper than the allocation-free stack
+    let mut tokens: Vec<_> = (0..301).map(|_| PostfixToken::Predicate(true)).collect();
+    tokens.extend((0..300).map(|_| PostfixToken::Operator(Operator::Xor)));
+    let compiled = CompiledExpression::from(PostfixExpression::from_tokens(tokens).unwrap());
+    assert!(compiled.evaluate(&()));
+    assert_eq!(compiled.predicates().len(), 301);
+
+    let mut tokens: Vec<_> = (0..301).map(|_| PostfixToken::Predicate(false)).collect();
+    tokens.extend((0..300).map(|_| PostfixToken::Operator(Operator::Iff)));
+    let compiled = PostfixExpression::from_tokens(tokens).unwrap().compile();
+    assert!(!compiled.evaluate(&()));
+}
+
+struct Failing;
+
+impl TryPredicateEvaluator for Failing {
+    type Predicate = char;
+    type Error = char;
+
+    fn try_evaluate_predicate(&self, predicate: &char) -> Result<bool, char> {
+        match predicate {
+            'a' => Ok(true),
+            p => Err(*p),
+        }
+    }
+}
+
+#[test]
+fn test_compiled_try_evaluate() {
+    assert_eq!(parse("a OR b").compile().try_evaluate(&Failing), Ok(true));
+    assert_eq!(
+        parse("a AND (c OR b)").compile().try_evaluate(&Failing),
+        Err('c')
+    );
+}
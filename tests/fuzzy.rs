@@ -0,0 +1,115 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{FuzzyLogic, PredicateEvaluator, ScoringEvaluator};
+use std::collections::HashMap;
+
+struct Scores(HashMap<&'static str, f64>);
+
+impl ScoringEvaluator for Scores {
+    type Predicate = String;
+
+    fn score_predicate(&self, predicate: &String) -> f64 {
+        self.0[predicate.as_str()]
+    }
+}
+
+impl PredicateEvaluator for Scores {
+    type Predicate = String;
+
+    fn evaluate_predicate(&self, predicate: &String) -> bool {
+        self.0[predicate.as_str()] == 1.0
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn test_evaluate_fuzzy() {
+    let scores = Scores(HashMap::from([("A", 0.8), ("B", 0.5), ("C", 0.25)]));
+    for (infix, min_max, product, lukasiewicz) in [
+        ("A", 0.8, 0.8, 0.8),
+        ("NOT A", 0.2, 0.2, 0.2),
+        ("A AND B", 0.5, 0.4, 0.3),
+        ("A OR B", 0.8, 0.9, 1.0),
+        ("B NAND C", 0.75, 0.875, 1.0),
+        ("B NOR C", 0.5, 0.375, 0.25),
+        ("A IMPLIES C", 0.25, 0.4, 0.45),
+        ("B XOR C", 0.5, 0.453125, 0.25),
+        ("B IFF C", 0.5, 0.546875, 0.75),
+        ("A AND (B OR C)", 0.5, 0.5, 0.55),
+    ] {
+        let postfix = parse(infix);
+        assert_close(postfix.evaluate_fuzzy(&scores, FuzzyLogic::MinMax), min_max);
+        assert_close(
+            postfix.evaluate_fuzzy(&scores, FuzzyLogic::Product),
+            product,
+        );
+        assert_close(
+            postfix.evaluate_fuzzy(&scores, FuzzyLogic::Lukasiewicz),
+            lukasiewicz,
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_fuzzy_crisp_matches_evaluate() {
+    let scores = Scores(HashMap::from([("A", 1.0), ("B", 0.0), ("C", 1.0)]));
+    for infix in [
+        "A AND B OR C",
+        "A NAND (B NOR C) IFF A",
+        "(A XOR B) IMPLIES NOT (C OR B)",
+    ] {
+        let postfix = parse(infix);
+        let expected = f64::from(postfix.evaluate(&scores));
+        for logic in [
+            FuzzyLogic::MinMax,
+            FuzzyLogic::Product,
+            FuzzyLogic::Lukasiewicz,
+        ] {
+            assert_eq!(postfix.evaluate_fuzzy(&scores, logic), expected, "{infix}");
+        }
+    }
+}
+
+#[test]
+fn test_evaluate_fuzzy_clamps_scores() {
+    let scores = Scores(HashMap::from([("A", 1.5), ("B", -0.5)]));
+    assert_eq!(
+        parse("A").evaluate_fuzzy(&scores, FuzzyLogic::default()),
+        1.0
+    );
+    assert_eq!(
+        parse("NOT B").evaluate_fuzzy(&scores, FuzzyLogic::default()),
+        1.0
+    );
+}
+
+#[test]
+fn test_evaluate_fuzzy_nan_score() {
+    let scores = Scores(HashMap::from([("A", 1.0), ("C", f64::NAN)]));
+    for logic in [
+        FuzzyLogic::MinMax,
+        FuzzyLogic::Product,
+        FuzzyLogic::Lukasiewicz,
+    ] {
+        assert_eq!(parse("C").evaluate_fuzzy(&scores, logic), 0.0);
+        assert_eq!(parse("A AND NOT C").evaluate_fuzzy(&scores, logic), 1.0);
+    }
+}
+
+#[test]
+fn test_evaluate_fuzzy_short_circuit() {
+    // `B` is missing from the scores, and is not evaluated when `A` is 0
+    let scores = Scores(HashMap::from([("A", 0.0)]));
+    assert_eq!(
+        parse("A AND B").evaluate_fuzzy(&scores, FuzzyLogic::Product),
+        0.0
+    );
+    assert_eq!(
+        parse("A IMPLIES B").evaluate_fuzzy(&scores, FuzzyLogic::Product),
+        1.0
+    );
+}
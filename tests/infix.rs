@@ -1,5 +1,6 @@
 use rpn_predicate_interpreter::{
-    InfixExpression, InfixToken, Operator, Parenthesis, PostfixExpression, PostfixToken,
+    InfixExpression, InfixToken, KeywordStyle, Operator, Parenthesis, PostfixExpression,
+    PostfixToken, ValidationError,
 };
 
 #[test]
@@ -496,3 +497,451 @@ fn test_infix_invalid_missing_predicate_before_close_parenthesis() {
     ]);
     assert!(infix.is_none());
 }
+
+#[test]
+// a*(b+c) --> abc+*
+fn test_infix_parse() {
+    let infix = InfixExpression::parse("a AND (b OR c)", |s| Some(s.to_string())).unwrap();
+    assert_eq!(
+        infix,
+        InfixExpression::from_tokens(vec![
+            InfixToken::Predicate("a".to_string()),
+            InfixToken::Operator(Operator::And),
+            InfixToken::Parenthesis(Parenthesis::Open),
+            InfixToken::Predicate("b".to_string()),
+            InfixToken::Operator(Operator::Or),
+            InfixToken::Predicate("c".to_string()),
+            InfixToken::Parenthesis(Parenthesis::Close),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+// ((a)+(b))*c --> ab+c*
+fn test_infix_parse_compact() {
+    let infix = InfixExpression::parse("((x>1)or(y<2))and\tz", Some).unwrap();
+    assert_eq!(
+        infix.to_postfix(),
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("x>1"),
+            PostfixToken::Predicate("y<2"),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Predicate("z"),
+            PostfixToken::Operator(Operator::And),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_infix_parse_predicate_parser() {
+    let infix = InfixExpression::parse("1 OR 22", |s| s.parse::<u8>().ok()).unwrap();
+    assert_eq!(
+        infix,
+        InfixExpression::from_tokens(vec![
+            InfixToken::Predicate(1),
+            InfixToken::Operator(Operator::Or),
+            InfixToken::Predicate(22),
+        ])
+        .unwrap()
+    );
+
+    assert!(InfixExpression::parse("1 OR 256", |s| s.parse::<u8>().ok()).is_none());
+}
+
+#[test]
+fn test_infix_parse_invalid() {
+    for input in ["", "a b", "a AND", "(a OR b", "a OR b)", "AND a", "a () b"] {
+        assert!(InfixExpression::parse(input, Some).is_none());
+    }
+}
+
+#[test]
+fn test_infix_try_from_tokens_errors() {
+    let cases = [
+        (vec![], ValidationError::EmptyExpression),
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Operator(Operator::And),
+                InfixToken::Operator(Operator::Or),
+                InfixToken::Predicate("b"),
+            ],
+            ValidationError::MissingOperand(2),
+        ),
+        (
+            vec![
+                InfixToken::Operator(Operator::And),
+                InfixToken::Predicate("a"),
+            ],
+            ValidationError::MissingOperand(0),
+        ),
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Operator(Operator::Or),
+                InfixToken::Predicate("b"),
+                InfixToken::Predicate("c"),
+            ],
+            ValidationError::AdjacentPredicates(3),
+        ),
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Operator(Operator::Or),
+            ],
+            ValidationError::TrailingOperator(1),
+        ),
+        (
+            vec![
+                InfixToken::Parenthesis(Parenthesis::Open),
+                InfixToken::Predicate("a"),
+                InfixToken::Operator(Operator::Or),
+                InfixToken::Parenthesis(Parenthesis::Open),
+                InfixToken::Predicate("b"),
+                InfixToken::Parenthesis(Parenthesis::Close),
+            ],
+            ValidationError::UnbalancedParenthesis(0),
+        ),
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Parenthesis(Parenthesis::Close),
+            ],
+            ValidationError::UnbalancedParenthesis(1),
+        ),
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Parenthesis(Parenthesis::Open),
+                InfixToken::Predicate("b"),
+                InfixToken::Parenthesis(Parenthesis::Close),
+            ],
+            ValidationError::MissingOperator(1),
+        ),
+        (
+            vec![
+                InfixToken::Parenthesis(Parenthesis::Open),
+                InfixToken::Parenthesis(Parenthesis::Close),
+            ],
+            ValidationError::MissingOperand(1),
+        ),
+    ];
+
+    for (tokens, error) in cases {
+        assert_eq!(InfixExpression::try_from_tokens(tokens), Err(error));
+    }
+}
+
+#[test]
+fn test_infix_try_from_tokens_ok() {
+    let tokens = vec![
+        InfixToken::Parenthesis(Parenthesis::Open),
+        InfixToken::Predicate("a"),
+        InfixToken::Parenthesis(Parenthesis::Close),
+        InfixToken::Operator(Operator::And),
+        InfixToken::Predicate("b"),
+    ];
+    assert!(InfixExpression::try_from_tokens(tokens).is_ok());
+}
+
+#[test]
+// !a*!(b+c) --> a!bc+!*
+fn test_infix_to_postfix_not() {
+    let infix = InfixExpression::from_tokens(vec![
+        InfixToken::Operator(Operator::Not),
+        InfixToken::Predicate("a"),
+        InfixToken::Operator(Operator::And),
+        InfixToken::Operator(Operator::Not),
+        InfixToken::Parenthesis(Parenthesis::Open),
+        InfixToken::Predicate("b"),
+        InfixToken::Operator(Operator::Or),
+        InfixToken::Predicate("c"),
+        InfixToken::Parenthesis(Parenthesis::Close),
+    ])
+    .unwrap();
+
+    let postfix = infix.to_postfix();
+    assert_eq!(
+        postfix,
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Predicate("c"),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Operator(Operator::And),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+// !!a+b --> a!!b+
+fn test_infix_to_postfix_double_not() {
+    let infix = InfixExpression::parse("NOT NOT a OR b", Some).unwrap();
+
+    let postfix = infix.to_postfix();
+    assert_eq!(
+        postfix,
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Operator(Operator::Or),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_infix_invalid_not() {
+    let cases = [
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Operator(Operator::Not),
+                InfixToken::Predicate("b"),
+            ],
+            ValidationError::MissingOperator(1),
+        ),
+        (
+            vec![
+                InfixToken::Predicate("a"),
+                InfixToken::Operator(Operator::And),
+                InfixToken::Operator(Operator::Not),
+            ],
+            ValidationError::TrailingOperator(2),
+        ),
+        (
+            vec![
+                InfixToken::Operator(Operator::Not),
+                InfixToken::Operator(Operator::And),
+                InfixToken::Predicate("a"),
+            ],
+            ValidationError::MissingOperand(1),
+        ),
+    ];
+
+    for (tokens, error) in cases {
+        assert_eq!(InfixExpression::try_from_tokens(tokens), Err(error));
+    }
+}
+
+#[test]
+// a->b->c --> abc->->
+fn test_infix_to_postfix_implies_right_associative() {
+    let infix = InfixExpression::parse("a IMPLIES b IMPLIES c", Some).unwrap();
+    assert_eq!(
+        infix.to_postfix(),
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Predicate("c"),
+            PostfixToken::Operator(Operator::Implies),
+            PostfixToken::Operator(Operator::Implies),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+// a op b op c --> ab op c op, for op in xor, nand, nor, iff
+fn test_infix_to_postfix_left_associative() {
+    for op in [Operator::Xor, Operator::Nand, Operator::Nor, Operator::Iff] {
+        let infix = InfixExpression::from_tokens(vec![
+            InfixToken::Predicate("a"),
+            InfixToken::Operator(op),
+            InfixToken::Predicate("b"),
+            InfixToken::Operator(op),
+            InfixToken::Predicate("c"),
+        ])
+        .unwrap();
+        assert_eq!(
+            infix.to_postfix(),
+            PostfixExpression::from_tokens(vec![
+                PostfixToken::Predicate("a"),
+                PostfixToken::Predicate("b"),
+                PostfixToken::Operator(op),
+                PostfixToken::Predicate("c"),
+                PostfixToken::Operator(op),
+            ])
+            .unwrap()
+        );
+    }
+}
+
+#[test]
+// a iff b -> c or d xor e and f nand g nor !h --> abcdef and g nand xor or h ! nor -> iff
+fn test_infix_to_postfix_precedence() {
+    let infix = InfixExpression::parse("a IFF b IMPLIES c OR d XOR e AND f NAND g NOR NOT h", Some)
+        .unwrap();
+    assert_eq!(
+        infix.to_postfix(),
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Predicate("c"),
+            PostfixToken::Predicate("d"),
+            PostfixToken::Predicate("e"),
+            PostfixToken::Predicate("f"),
+            PostfixToken::Operator(Operator::And),
+            PostfixToken::Predicate("g"),
+            PostfixToken::Operator(Operator::Nand),
+            PostfixToken::Operator(Operator::Xor),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Predicate("h"),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Operator(Operator::Nor),
+            PostfixToken::Operator(Operator::Implies),
+            PostfixToken::Operator(Operator::Iff),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_infix_display() {
+    let infix = InfixExpression::parse("NOT a AND ((b OR c)) IMPLIES NOT (d)", Some).unwrap();
+    assert_eq!(infix.to_string(), "NOT a AND ((b OR c)) IMPLIES NOT (d)");
+    assert_eq!(
+        infix.display(KeywordStyle::Symbols).to_string(),
+        "! a && ((b || c)) -> ! (d)"
+    );
+    assert_eq!(
+        infix.display(KeywordStyle::Logic).to_string(),
+        "¬ a ∧ ((b ∨ c)) → ¬ (d)"
+    );
+}
+
+#[test]
+fn test_infix_tokens_display() {
+    assert_eq!(InfixToken::Predicate(1).to_string(), "1");
+    assert_eq!(InfixToken::<u8>::Operator(Operator::Xor).to_string(), "XOR");
+    assert_eq!(
+        InfixToken::<u8>::Parenthesis(Parenthesis::Open).to_string(),
+        "("
+    );
+    assert_eq!(Parenthesis::Close.to_string(), ")");
+    assert_eq!(Operator::Nand.to_string(), "NAND");
+}
+
+#[test]
+// symbols are only recognized as separate lexemes
+fn test_infix_parse_symbols_need_separators() {
+    assert_eq!(
+        InfixExpression::parse("a&&b", Some).unwrap(),
+        InfixExpression::from_tokens(vec![InfixToken::Predicate("a&&b")]).unwrap()
+    );
+    assert_eq!(
+        InfixExpression::parse("!a", Some).unwrap(),
+        InfixExpression::from_tokens(vec![InfixToken::Predicate("!a")]).unwrap()
+    );
+    assert_eq!(
+        InfixExpression::parse("! a && !(b)", Some).unwrap(),
+        InfixExpression::parse("NOT a AND NOT (b)", Some).unwrap()
+    );
+}
+
+#[test]
+fn test_infix_display_round_trip() {
+    for infix in [
+        "a AND (b OR NOT c) IFF d",
+        "NOT a AND (b OR NOT (c IMPLIES d))",
+        "NOT NOT a NAND b NOR NOT (c XOR d)",
+    ] {
+        let words = InfixExpression::parse(infix, Some).unwrap();
+        for style in [
+            KeywordStyle::Words,
+            KeywordStyle::Symbols,
+            KeywordStyle::Logic,
+        ] {
+            let text = words.display(style).to_string();
+            assert_eq!(
+                InfixExpression::parse(&text, Some).unwrap(),
+                words,
+                "{text}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_infix_map() {
+    let infix = InfixExpression::parse("(1 OR 2) AND NOT 3", |s| Some(s.to_string())).unwrap();
+
+    let lengths = infix.map_ref(String::len);
+    assert_eq!(lengths.to_string(), "(1 OR 1) AND NOT 1");
+
+    let numbers = infix.map(|s| s.parse::<u8>().unwrap() * 10);
+    assert_eq!(
+        numbers,
+        InfixExpression::from_tokens(vec![
+            InfixToken::Parenthesis(Parenthesis::Open),
+            InfixToken::Predicate(10),
+            InfixToken::Operator(Operator::Or),
+            InfixToken::Predicate(20),
+            InfixToken::Parenthesis(Parenthesis::Close),
+            InfixToken::Operator(Operator::And),
+            InfixToken::Operator(Operator::Not),
+            InfixToken::Predicate(30),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_infix_try_map() {
+    let infix = || InfixExpression::parse("a OR (b AND c) OR d", Some).unwrap();
+
+    let upper = infix().try_map(|s| Ok::<_, ()>(s.to_uppercase())).unwrap();
+    assert_eq!(upper.to_string(), "A OR (B AND C) OR D");
+
+    let mut calls = Vec::new();
+    let error = infix()
+        .try_map(|s| {
+            calls.push(s);
+            if s == "c" {
+                Err("unresolved")
+            } else {
+                Ok(s.len())
+            }
+        })
+        .unwrap_err();
+    assert_eq!(error.index(), 2);
+    assert_eq!(*error.error(), "unresolved");
+    assert_eq!(error.to_string(), "predicate 2: unresolved");
+    assert_eq!(calls, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_infix_tokens_access() {
+    let infix = InfixExpression::parse("NOT (a OR b) AND c", Some).unwrap();
+
+    assert_eq!(infix.len(), 8);
+    assert!(!infix.is_empty());
+    assert_eq!(infix.tokens()[0], InfixToken::Operator(Operator::Not));
+    assert_eq!(
+        infix.predicates().copied().collect::<Vec<_>>(),
+        ["a", "b", "c"]
+    );
+    assert_eq!(infix.iter().count(), infix.len());
+    assert_eq!(
+        (&infix)
+            .into_iter()
+            .filter(|token| matches!(token, InfixToken::Parenthesis(_)))
+            .count(),
+        2
+    );
+
+    let tokens: Vec<_> = infix.into_iter().collect();
+    assert_eq!(
+        InfixExpression::from_tokens(tokens).unwrap().into_tokens(),
+        InfixExpression::parse("NOT (a OR b) AND c", Some)
+            .unwrap()
+            .tokens()
+    );
+}
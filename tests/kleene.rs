@@ -0,0 +1,111 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{PredicateEvaluator, TriStatePredicateEvaluator, Truth};
+use std::cell::RefCell;
+
+/// Predicates are single letters: upper case is true, lower case is false, `?` is unknown.
+struct Letters {
+    calls: RefCell<Vec<char>>,
+}
+
+impl Letters {
+    fn new() -> Self {
+        Self {
+            calls: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl TriStatePredicateEvaluator for Letters {
+    type Predicate = char;
+
+    fn evaluate_tri_state_predicate(&self, predicate: &char) -> Truth {
+        self.calls.borrow_mut().push(*predicate);
+        match predicate {
+            '?' => Truth::Unknown,
+            c => Truth::from(c.is_ascii_uppercase()),
+        }
+    }
+}
+
+impl PredicateEvaluator for Letters {
+    type Predicate = char;
+
+    fn evaluate_predicate(&self, predicate: &char) -> bool {
+        predicate.is_ascii_uppercase()
+    }
+}
+
+#[test]
+fn test_evaluate_kleene() {
+    for (infix, expected) in [
+        ("?", Truth::Unknown),
+        ("NOT ?", Truth::Unknown),
+        ("a AND ?", Truth::False),
+        ("A AND ?", Truth::Unknown),
+        ("? OR A", Truth::True),
+        ("? OR a", Truth::Unknown),
+        ("? XOR A", Truth::Unknown),
+        ("? IMPLIES A", Truth::True),
+        ("a IMPLIES ?", Truth::True),
+        ("? NAND a", Truth::True),
+        ("? NOR A", Truth::False),
+        ("? IFF ?", Truth::Unknown),
+        ("(? OR A) AND (b OR ?)", Truth::Unknown),
+        ("(? OR A) AND NOT (b AND ?)", Truth::True),
+    ] {
+        assert_eq!(
+            parse(infix).evaluate_kleene(&Letters::new()),
+            expected,
+            "{infix}"
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_kleene_known_matches_evaluate() {
+    for infix in [
+        "A AND b OR C",
+        "a NAND (B NOR c) IFF D",
+        "(A XOR b) IMPLIES NOT (c OR D)",
+    ] {
+        let postfix = parse(infix);
+        let letters = Letters::new();
+        assert_eq!(
+            postfix.evaluate_kleene(&letters),
+            Truth::from(postfix.evaluate(&letters)),
+            "{infix}"
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_kleene_short_circuit() {
+    for (infix, calls) in [
+        ("a AND ?", "a"),
+        ("? AND a", "?a"),
+        ("A OR ? AND b", "A"),
+        ("? OR A OR b", "?A"),
+    ] {
+        let letters = Letters::new();
+        parse(infix).evaluate_kleene(&letters);
+        assert_eq!(
+            letters.calls.into_inner().into_iter().collect::<String>(),
+            calls,
+            "{infix}"
+        );
+    }
+}
+
+#[test]
+fn test_truth_operators() {
+    assert_eq!(Truth::True & Truth::Unknown, Truth::Unknown);
+    assert_eq!(Truth::False & Truth::Unknown, Truth::False);
+    assert_eq!(Truth::True | Truth::Unknown, Truth::True);
+    assert_eq!(Truth::False | Truth::Unknown, Truth::Unknown);
+    assert_eq!(!Truth::Unknown, Truth::Unknown);
+    assert_eq!(!Truth::True, Truth::False);
+    assert_eq!(Truth::Unknown.to_bool(), None);
+    assert_eq!(Truth::from(true).to_bool(), Some(true));
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{
+    PartialEvaluation, PartialPredicateEvaluator, PostfixExpression, PredicateEvaluator,
+};
+
+/// Knows the values of the predicates listed, by name.
+struct Known(&'static [(&'static str, bool)]);
+
+impl PartialPredicateEvaluator for Known {
+    type Predicate = String;
+
+    fn partially_evaluate_predicate(&self, predicate: &String) -> Option<bool> {
+        self.0
+            .iter()
+            .find(|(name, _)| name == predicate)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Gives every predicate a value from the bits of a number, by index in the alphabet.
+struct Assignment(u32);
+
+impl PredicateEvaluator for Assignment {
+    type Predicate = String;
+
+    fn evaluate_predicate(&self, predicate: &String) -> bool {
+        let bit = u32::from(predicate.as_bytes()[0] - b'a');
+        self.0 & (1 << bit) != 0
+    }
+}
+
+fn residual(infix: &str, known: &'static [(&'static str, bool)]) -> String {
+    match parse(infix).partially_evaluate(&Known(known)) {
+        PartialEvaluation::Residual(residual) => residual.to_string(),
+        PartialEvaluation::Value(value) => value.to_string(),
+    }
+}
+
+// (infix, known predicate values, residual or value)
+type PartialCase = (&'static str, &'static [(&'static str, bool)], &'static str);
+
+#[test]
+fn test_partially_evaluate() {
+    let cases: [PartialCase; 12] = [
+        (
+            "tenant AND (region OR x)",
+            &[("tenant", true), ("region", false)],
+            "x",
+        ),
+        ("tenant AND (region OR x)", &[("tenant", false)], "false"),
+        ("tenant AND (region OR x)", &[("region", true)], "tenant"),
+        ("tenant AND (region OR x)", &[], "tenant AND (region OR x)"),
+        ("a XOR x", &[("a", true)], "NOT x"),
+        ("NOT x XOR a", &[("a", true)], "x"),
+        ("x IMPLIES a", &[("a", false)], "NOT x"),
+        ("x IMPLIES a", &[("a", true)], "true"),
+        ("a NAND x", &[("a", true)], "NOT x"),
+        ("x NOR a", &[("a", false)], "NOT x"),
+        ("a IFF (x OR y)", &[("a", false)], "NOT (x OR y)"),
+        ("NOT a AND b", &[("a", false), ("b", true)], "true"),
+    ];
+    for (infix, known, expected) in cases {
+        assert_eq!(residual(infix, known), expected, "{infix}");
+    }
+}
+
+#[test]
+fn test_partially_evaluate_matches_evaluate() {
+    let known: [&[(&str, bool)]; 4] = [
+        &[("a", true)],
+        &[("a", false), ("c", true)],
+        &[("b", false), ("d", false)],
+        &[("a", true), ("b", true), ("e", false)],
+    ];
+    for infix in [
+        "a AND b OR c AND d",
+        "(a OR b) AND NOT (c XOR d) IMPLIES e",
+        "a NAND (b NOR c) IFF d",
+        "NOT (NOT a AND NOT b) OR e",
+    ] {
+        let postfix = parse(infix);
+        for known in known {
+            let partial = postfix.partially_evaluate(&Known(known));
+            for bits in 0..32 {
+                // complete the known values with the assignment
+                let bits = known.iter().fold(bits, |bits, (name, value)| {
+                    let bit = 1 << (name.as_bytes()[0] - b'a');
+                    if *value {
+                        bits | bit
+                    } else {
+                        bits & !bit
+                    }
+                });
+                let expected = postfix.evaluate(&Assignment(bits));
+                match &partial {
+                    PartialEvaluation::Value(value) => assert_eq!(*value, expected),
+                    PartialEvaluation::Residual(residual) => {
+                        assert_eq!(residual.evaluate(&Assignment(bits)), expected);
+                        for (name, _) in known {
+                            assert!(residual.predicates().all(|p| p != name));
+                        }
+                        let tokens = residual.map_ref(String::clone).into_tokens();
+                        assert!(PostfixExpression::from_tokens(tokens).is_some());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
+#![allow(clippy::useless_vec)]
+
 use rpn_predicate_interpreter::{
-    InfixExpression, InfixToken, Operator, Parenthesis, PostfixExpression, PostfixToken,
-    PredicateEvaluator,
+    EvalContext, InfixExpression, InfixToken, KeywordStyle, Operator, Parenthesis, ParseError,
+    PostfixExpression, PostfixToken, PredicateEvaluator, TryPredicateEvaluator, ValidationError,
 };
+use std::cell::RefCell;
 
 struct Predicate {
     condition: PredicateCondition,
@@ -548,3 +551,883 @@ fn test_postfix_invalid_too_many_predicates_bis() {
     ]);
     assert!(postfix.is_none());
 }
+
+#[test]
+fn test_postfix_try_from_tokens_errors() {
+    let cases = [
+        (vec![], ValidationError::EmptyExpression),
+        (
+            vec![
+                PostfixToken::Predicate("a"),
+                PostfixToken::Operator(Operator::And),
+            ],
+            ValidationError::MissingOperand(1),
+        ),
+        (
+            vec![
+                PostfixToken::Operator(Operator::Or),
+                PostfixToken::Predicate("a"),
+                PostfixToken::Predicate("b"),
+            ],
+            ValidationError::MissingOperand(0),
+        ),
+        (
+            vec![
+                PostfixToken::Predicate("a"),
+                PostfixToken::Predicate("b"),
+                PostfixToken::Predicate("c"),
+                PostfixToken::Operator(Operator::Or),
+            ],
+            ValidationError::MissingOperator(4),
+        ),
+    ];
+
+    for (tokens, error) in cases {
+        assert_eq!(PostfixExpression::try_from_tokens(tokens), Err(error));
+    }
+}
+
+#[test]
+// ab*!c!+ --> !(a*b)+!c
+fn test_postfix_to_infix_not() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Operator(Operator::And),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Operator(Operator::Or),
+    ])
+    .unwrap();
+
+    let infix = postfix.to_infix();
+    assert_eq!(
+        infix,
+        InfixExpression::from_tokens(vec![
+            InfixToken::Operator(Operator::Not),
+            InfixToken::Parenthesis(Parenthesis::Open),
+            InfixToken::Predicate("a"),
+            InfixToken::Operator(Operator::And),
+            InfixToken::Predicate("b"),
+            InfixToken::Parenthesis(Parenthesis::Close),
+            InfixToken::Operator(Operator::Or),
+            InfixToken::Operator(Operator::Not),
+            InfixToken::Predicate("c"),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_postfix_evaluate_not() {
+    for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+        // a!b*
+        let expr = PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate(a),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Predicate(b),
+            PostfixToken::Operator(Operator::And),
+        ])
+        .unwrap();
+        assert_eq!(expr.evaluate(&()), !a && b);
+
+        // ab+!!
+        let expr = PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate(a),
+            PostfixToken::Predicate(b),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Operator(Operator::Not),
+        ])
+        .unwrap();
+        assert_eq!(expr.evaluate(&()), a || b);
+    }
+}
+
+#[test]
+fn test_postfix_invalid_not() {
+    let postfix =
+        PostfixExpression::<u8>::try_from_tokens(vec![PostfixToken::Operator(Operator::Not)]);
+    assert_eq!(postfix, Err(ValidationError::MissingOperand(0)));
+
+    let postfix = PostfixExpression::try_from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Operator(Operator::Not),
+    ]);
+    assert_eq!(postfix, Err(ValidationError::MissingOperator(3)));
+}
+
+#[test]
+fn test_postfix_evaluate_binary_operators() {
+    for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+        for (op, expected) in [
+            (Operator::And, a && b),
+            (Operator::Or, a || b),
+            (Operator::Xor, a != b),
+            (Operator::Nand, !(a && b)),
+            (Operator::Nor, !(a || b)),
+            (Operator::Implies, !a || b),
+            (Operator::Iff, a == b),
+        ] {
+            let expr = PostfixExpression::from_tokens(vec![
+                PostfixToken::Predicate(a),
+                PostfixToken::Predicate(b),
+                PostfixToken::Operator(op),
+            ])
+            .unwrap();
+            assert_eq!(expr.evaluate(&()), expected);
+        }
+    }
+}
+
+#[test]
+// abc*-> --> a->(b*c)
+fn test_postfix_evaluate_implies_nested() {
+    for (a, b, c) in [
+        (false, true, false),
+        (true, false, false),
+        (true, true, false),
+    ] {
+        let expr = PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate(a),
+            PostfixToken::Predicate(b),
+            PostfixToken::Predicate(c),
+            PostfixToken::Operator(Operator::And),
+            PostfixToken::Operator(Operator::Implies),
+        ])
+        .unwrap();
+        assert_eq!(expr.evaluate(&()), !a || (b && c));
+    }
+}
+
+#[test]
+// ab->c-> --> (a->b)->c
+// abc->-> --> a->b->c
+fn test_postfix_to_infix_implies() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Operator(Operator::Implies),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::Implies),
+    ])
+    .unwrap();
+    assert_eq!(
+        postfix.to_infix(),
+        InfixExpression::parse("(a IMPLIES b) IMPLIES c", Some).unwrap()
+    );
+
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::Implies),
+        PostfixToken::Operator(Operator::Implies),
+    ])
+    .unwrap();
+    assert_eq!(
+        postfix.to_infix(),
+        InfixExpression::parse("a IMPLIES b IMPLIES c", Some).unwrap()
+    );
+}
+
+#[test]
+// abc nand nand --> a nand (b nand c)
+// abc nand and --> a and (b nand c)
+// ab nand c and --> a nand b and c
+fn test_postfix_to_infix_non_associative() {
+    for (outer, inner, expected) in [
+        (Operator::Nand, Operator::Nand, "a NAND (b NAND c)"),
+        (Operator::Nor, Operator::Nor, "a NOR (b NOR c)"),
+        (Operator::And, Operator::Nand, "a AND (b NAND c)"),
+        (Operator::Xor, Operator::Xor, "a XOR b XOR c"),
+    ] {
+        let postfix = PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Predicate("c"),
+            PostfixToken::Operator(inner),
+            PostfixToken::Operator(outer),
+        ])
+        .unwrap();
+        assert_eq!(
+            postfix.to_infix(),
+            InfixExpression::parse(expected, Some).unwrap()
+        );
+    }
+
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Operator(Operator::Nand),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::And),
+    ])
+    .unwrap();
+    assert_eq!(
+        postfix.to_infix(),
+        InfixExpression::parse("a NAND b AND c", Some).unwrap()
+    );
+}
+
+struct Assignment(u32);
+
+impl PredicateEvaluator for Assignment {
+    type Predicate = usize;
+
+    fn evaluate_predicate(&self, predicate: &usize) -> bool {
+        self.0 & (1 << predicate) != 0
+    }
+}
+
+/// Generates pseudo-random expressions over the predicates `0..4`, with every operator.
+fn random_postfix(seed: &mut u64, predicate_count: usize) -> PostfixExpression<usize> {
+    let mut next = |bound: usize| {
+        // xorshift64
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        (*seed % bound as u64) as usize
+    };
+    let operators = [
+        Operator::And,
+        Operator::Or,
+        Operator::Not,
+        Operator::Xor,
+        Operator::Nand,
+        Operator::Nor,
+        Operator::Implies,
+        Operator::Iff,
+    ];
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    for _ in 0..predicate_count {
+        tokens.push(PostfixToken::Predicate(next(4)));
+        depth += 1;
+        while depth > 1 && next(2) == 0 {
+            let op = operators[next(operators.len())];
+            tokens.push(PostfixToken::Operator(op));
+            if op != Operator::Not {
+                depth -= 1;
+            }
+        }
+    }
+    for _ in 1..depth {
+        tokens.push(PostfixToken::Operator(operators[next(2)]));
+    }
+    PostfixExpression::from_tokens(tokens).unwrap()
+}
+
+fn assert_equivalent(a: &PostfixExpression<usize>, b: &PostfixExpression<usize>) {
+    for bits in 0..16 {
+        assert_eq!(
+            a.evaluate(&Assignment(bits)),
+            b.evaluate(&Assignment(bits)),
+            "{a:?} and {b:?} differ on {bits:04b}"
+        );
+    }
+}
+
+#[test]
+// a(bc nand d and)and --> a and (b nand c and d)
+// a(bc nor d or)or --> a or (b nor c or d)
+fn test_postfix_to_infix_keeps_meaning() {
+    for (outer, inner) in [
+        (Operator::And, Operator::Nand),
+        (Operator::Or, Operator::Nor),
+        (Operator::Nand, Operator::And),
+    ] {
+        let tokens = || {
+            vec![
+                PostfixToken::Predicate(0),
+                PostfixToken::Predicate(1),
+                PostfixToken::Predicate(2),
+                PostfixToken::Operator(inner),
+                PostfixToken::Predicate(3),
+                PostfixToken::Operator(outer),
+                PostfixToken::Operator(outer),
+            ]
+        };
+        let postfix = PostfixExpression::from_tokens(tokens()).unwrap();
+        let round_trip = PostfixExpression::from_tokens(tokens())
+            .unwrap()
+            .to_infix()
+            .to_postfix();
+        assert_equivalent(&postfix, &round_trip);
+    }
+
+    let mut seed = 0x9e37_79b9_7f4a_7c15;
+    for _ in 0..2000 {
+        let mut copy = seed;
+        let postfix = random_postfix(&mut seed, 6);
+        let round_trip = random_postfix(&mut copy, 6).to_infix().to_postfix();
+        assert_equivalent(&postfix, &round_trip);
+    }
+}
+
+#[test]
+// abc+* --> a*(b+c)
+fn test_postfix_display() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::Or),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Operator(Operator::And),
+    ])
+    .unwrap();
+
+    assert_eq!(postfix.to_string(), "a AND NOT (b OR c)");
+    assert_eq!(
+        postfix.display(KeywordStyle::Symbols).to_string(),
+        "a && ! (b || c)"
+    );
+    assert_eq!(
+        postfix.display(KeywordStyle::Logic).to_string(),
+        "a ∧ ¬ (b ∨ c)"
+    );
+    assert_eq!(postfix.to_string(), postfix.to_infix().to_string());
+}
+
+#[test]
+fn test_postfix_tokens_display() {
+    assert_eq!(PostfixToken::Predicate("a").to_string(), "a");
+    assert_eq!(
+        PostfixToken::<u8>::Operator(Operator::Implies).to_string(),
+        "IMPLIES"
+    );
+}
+
+#[test]
+fn test_postfix_display_round_trip() {
+    let mut seed = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..500 {
+        let postfix = random_postfix(&mut seed, 5);
+        for style in [
+            KeywordStyle::Words,
+            KeywordStyle::Symbols,
+            KeywordStyle::Logic,
+        ] {
+            let text = postfix.display(style).to_string();
+            let infix = InfixExpression::parse(&text, |s| s.parse().ok()).unwrap();
+            assert_equivalent(&postfix, &infix.to_postfix());
+        }
+    }
+}
+
+struct Lookup {
+    calls: RefCell<Vec<&'static str>>,
+}
+
+impl TryPredicateEvaluator for Lookup {
+    type Predicate = &'static str;
+    type Error = String;
+
+    fn try_evaluate_predicate(&self, predicate: &Self::Predicate) -> Result<bool, Self::Error> {
+        self.calls.borrow_mut().push(predicate);
+        match *predicate {
+            "t" => Ok(true),
+            "f" => Ok(false),
+            other => Err(format!("unknown predicate {other}")),
+        }
+    }
+}
+
+#[test]
+// t*f+t --> tf*t+
+fn test_postfix_try_evaluate() {
+    let expr = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("t"),
+        PostfixToken::Predicate("f"),
+        PostfixToken::Operator(Operator::And),
+        PostfixToken::Predicate("t"),
+        PostfixToken::Operator(Operator::Or),
+    ])
+    .unwrap();
+    let lookup = Lookup {
+        calls: RefCell::new(Vec::new()),
+    };
+
+    assert_eq!(expr.try_evaluate(&lookup), Ok(true));
+    assert_eq!(*lookup.calls.borrow(), vec!["t", "f", "t"]);
+}
+
+#[test]
+// t*x+y --> tx*y+
+fn test_postfix_try_evaluate_error() {
+    let expr = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("t"),
+        PostfixToken::Predicate("x"),
+        PostfixToken::Operator(Operator::And),
+        PostfixToken::Predicate("y"),
+        PostfixToken::Operator(Operator::Or),
+    ])
+    .unwrap();
+    let lookup = Lookup {
+        calls: RefCell::new(Vec::new()),
+    };
+
+    assert_eq!(
+        expr.try_evaluate(&lookup),
+        Err("unknown predicate x".to_string())
+    );
+    assert_eq!(*lookup.calls.borrow(), vec!["t", "x"]);
+}
+
+struct Recorder {
+    calls: RefCell<Vec<char>>,
+}
+
+impl PredicateEvaluator for Recorder {
+    type Predicate = (char, bool);
+
+    fn evaluate_predicate(&self, predicate: &Self::Predicate) -> bool {
+        self.calls.borrow_mut().push(predicate.0);
+        predicate.1
+    }
+}
+
+fn evaluate_recording(infix: &str, values: &[(char, bool)]) -> (bool, Vec<char>) {
+    let expr = InfixExpression::parse(infix, |s| {
+        let name = s.chars().next()?;
+        values.iter().find(|(n, _)| *n == name).copied()
+    })
+    .unwrap()
+    .to_postfix();
+    let recorder = Recorder {
+        calls: RefCell::new(Vec::new()),
+    };
+    let result = expr.evaluate(&recorder);
+    (result, recorder.calls.into_inner())
+}
+
+// (infix, predicate values, result, evaluated predicates)
+type ShortCircuitCase = (&'static str, &'static [(char, bool)], bool, &'static [char]);
+
+#[test]
+fn test_postfix_evaluate_short_circuit() {
+    let cases: [ShortCircuitCase; 12] = [
+        ("a AND b", &[('a', false), ('b', true)], false, &['a']),
+        ("a AND b", &[('a', true), ('b', true)], true, &['a', 'b']),
+        ("a OR b", &[('a', true), ('b', false)], true, &['a']),
+        ("a NAND b", &[('a', false), ('b', true)], true, &['a']),
+        ("a NOR b", &[('a', true), ('b', false)], false, &['a']),
+        ("a IMPLIES b", &[('a', false), ('b', false)], true, &['a']),
+        ("a XOR b", &[('a', true), ('b', true)], false, &['a', 'b']),
+        ("a IFF b", &[('a', false), ('b', true)], false, &['a', 'b']),
+        (
+            "a AND (b OR c)",
+            &[('a', false), ('b', true), ('c', true)],
+            false,
+            &['a'],
+        ),
+        (
+            "a AND (b OR c)",
+            &[('a', true), ('b', true), ('c', false)],
+            true,
+            &['a', 'b'],
+        ),
+        (
+            "(a OR b) AND (c OR d)",
+            &[('a', true), ('b', true), ('c', false), ('d', true)],
+            true,
+            &['a', 'c', 'd'],
+        ),
+        ("NOT a AND b", &[('a', true), ('b', true)], false, &['a']),
+    ];
+
+    for (infix, values, result, calls) in cases {
+        assert_eq!(
+            evaluate_recording(infix, values),
+            (result, calls.to_vec()),
+            "{infix}"
+        );
+    }
+}
+
+#[test]
+// a*b*c*d with a false skips every other predicate
+fn test_postfix_evaluate_short_circuit_chain() {
+    let values = [('a', false), ('b', true), ('c', true), ('d', true)];
+    assert_eq!(
+        evaluate_recording("a AND b AND c AND d", &values),
+        (false, vec!['a'])
+    );
+    assert_eq!(
+        evaluate_recording("a AND (b AND (c AND d))", &values),
+        (false, vec!['a'])
+    );
+    assert_eq!(
+        evaluate_recording("(b AND a AND c) OR d", &values),
+        (true, vec!['b', 'a', 'd'])
+    );
+}
+
+#[test]
+// ab+c* evaluates operands left to right, even when the right one is a single predicate
+fn test_postfix_evaluate_left_to_right() {
+    let values = [('a', false), ('b', false), ('c', true)];
+    assert_eq!(
+        evaluate_recording("c AND (a OR b)", &values),
+        (false, vec!['c', 'a', 'b'])
+    );
+    assert_eq!(
+        evaluate_recording("(a OR b) AND c", &values),
+        (false, vec!['a', 'b'])
+    );
+}
+
+/// A record whose fields are referenced by index from predicates.
+struct Row(Vec<bool>);
+
+impl PredicateEvaluator for Row {
+    type Predicate = usize;
+
+    fn evaluate_predicate(&self, predicate: &usize) -> bool {
+        self.0[*predicate]
+    }
+}
+
+#[test]
+fn test_postfix_evaluate_with_context() {
+    let mut context = EvalContext::new();
+    let row = Row(vec![true, false, true]);
+    for (rpn, result) in [
+        ("0 1 2 OR AND", true),
+        ("1", false),
+        ("0 1 AND 2 AND NOT 1 OR", true),
+        ("0 1 XOR 2 IFF", true),
+        ("0 1 IMPLIES", false),
+    ] {
+        let postfix = PostfixExpression::parse(rpn, |s| s.parse().ok()).unwrap();
+        assert_eq!(postfix.evaluate_with(&mut context, &row), result, "{rpn}");
+        assert_eq!(postfix.evaluate(&row), result, "{rpn}");
+    }
+}
+
+#[test]
+fn test_postfix_evaluate_many() {
+    let postfix = PostfixExpression::parse("0 1 AND 2 OR", |s| s.parse().ok()).unwrap();
+    let rows = [
+        Row(vec![true, true, false]),
+        Row(vec![true, false, false]),
+        Row(vec![false, false, true]),
+        Row(vec![false, true, false]),
+    ];
+    let mut context = EvalContext::new();
+    assert_eq!(
+        postfix.evaluate_many(&mut context, &rows),
+        vec![true, false, true, false]
+    );
+    assert_eq!(postfix.evaluate_many(&mut context, &rows[..0]), vec![]);
+
+    let postfix = PostfixExpression::parse("0 NOT", |s| s.parse().ok()).unwrap();
+    assert_eq!(
+        postfix.evaluate_many(&mut context, &rows),
+        vec![false, false, true, true]
+    );
+}
+
+#[test]
+fn test_postfix_map() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("x"),
+        PostfixToken::Predicate("yy"),
+        PostfixToken::Operator(Operator::Xor),
+        PostfixToken::Operator(Operator::Not),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        postfix.map_ref(|s| s.len()),
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate(1),
+            PostfixToken::Predicate(2),
+            PostfixToken::Operator(Operator::Xor),
+            PostfixToken::Operator(Operator::Not),
+        ])
+        .unwrap()
+    );
+
+    let mapped = postfix.map(|s| s == "x");
+    assert!(!mapped.evaluate(&()));
+}
+
+#[test]
+fn test_postfix_try_map() {
+    let postfix = || {
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("1"),
+            PostfixToken::Predicate("2"),
+            PostfixToken::Operator(Operator::And),
+            PostfixToken::Predicate("x"),
+            PostfixToken::Operator(Operator::Or),
+        ])
+        .unwrap()
+    };
+
+    let error = postfix().try_map(|s| s.parse::<u8>()).unwrap_err();
+    assert_eq!(error.index(), 2);
+    assert!(error.into_error().to_string().contains("invalid digit"));
+
+    let parsed = postfix()
+        .map_ref(|s| *s)
+        .try_map(|s| s.parse::<u8>().or(Ok::<_, ()>(0)))
+        .unwrap();
+    assert_eq!(parsed.to_string(), "1 AND 2 OR 0");
+}
+
+#[test]
+// abc+!*
+fn test_postfix_tokens_access() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::Or),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Operator(Operator::And),
+    ])
+    .unwrap();
+
+    assert_eq!(postfix.len(), 6);
+    assert!(!postfix.is_empty());
+    assert_eq!(postfix.tokens()[3], PostfixToken::Operator(Operator::Or));
+    assert_eq!(
+        postfix.predicates().copied().collect::<Vec<_>>(),
+        ["a", "b", "c"]
+    );
+
+    let mut operators = Vec::new();
+    for token in &postfix {
+        if let PostfixToken::Operator(op) = token {
+            operators.push(*op);
+        }
+    }
+    assert_eq!(operators, [Operator::Or, Operator::Not, Operator::And]);
+    assert!(postfix.iter().eq(postfix.tokens().iter()));
+
+    let tokens = postfix.into_tokens();
+    assert_eq!(tokens.len(), 6);
+    assert!(PostfixExpression::from_tokens(tokens)
+        .unwrap()
+        .into_iter()
+        .any(|token| token == PostfixToken::Predicate("c")));
+}
+
+#[test]
+// abc+* --> a*(b+c)
+fn test_postfix_parse() {
+    let postfix = PostfixExpression::parse("a b c OR AND", |s| Some(s.to_string())).unwrap();
+    assert_eq!(
+        postfix,
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a".to_string()),
+            PostfixToken::Predicate("b".to_string()),
+            PostfixToken::Predicate("c".to_string()),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Operator(Operator::And),
+        ])
+        .unwrap()
+    );
+    assert_eq!(postfix.to_string(), "a AND (b OR c)");
+}
+
+#[test]
+fn test_postfix_parse_quoted() {
+    let postfix = PostfixExpression::parse(r#" "x > 1" "and" not && "say \"hi\\\"" || "#, |s| {
+        Some(s.to_string())
+    })
+    .unwrap();
+    assert_eq!(
+        postfix,
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("x > 1".to_string()),
+            PostfixToken::Predicate("and".to_string()),
+            PostfixToken::Operator(Operator::Not),
+            PostfixToken::Operator(Operator::And),
+            PostfixToken::Predicate(r#"say "hi\""#.to_string()),
+            PostfixToken::Operator(Operator::Or),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_postfix_parse_invalid() {
+    for (input, error) in [
+        ("", ParseError::Invalid(ValidationError::EmptyExpression)),
+        (
+            "a b",
+            ParseError::Invalid(ValidationError::MissingOperator(2)),
+        ),
+        (
+            "a AND",
+            ParseError::Invalid(ValidationError::MissingOperand(1)),
+        ),
+        (
+            "AND a b",
+            ParseError::Invalid(ValidationError::MissingOperand(0)),
+        ),
+        (r#"a "b OR"#, ParseError::UnterminatedQuote(2)),
+        (r#""a"b c OR"#, ParseError::UnexpectedCharacter(3)),
+        (r#"a b"c OR"#, ParseError::UnexpectedCharacter(3)),
+        (r#""a\x" b OR"#, ParseError::InvalidEscape(2)),
+    ] {
+        assert_eq!(
+            PostfixExpression::parse(input, |s| Some(s.to_string())),
+            Err(error),
+            "{input}"
+        );
+    }
+    assert_eq!(
+        PostfixExpression::parse("1 2 300 OR OR", |s| s.parse::<u8>().ok()),
+        Err(ParseError::InvalidPredicate(2))
+    );
+}
+
+#[test]
+fn test_postfix_rpn_round_trip() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a".to_string()),
+        PostfixToken::Predicate("b c".to_string()),
+        PostfixToken::Predicate("OR".to_string()),
+        PostfixToken::Operator(Operator::Implies),
+        PostfixToken::Predicate(String::new()),
+        PostfixToken::Predicate(r#"\"quoted\""#.to_string()),
+        PostfixToken::Operator(Operator::Nand),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Operator(Operator::Xor),
+        PostfixToken::Operator(Operator::And),
+    ])
+    .unwrap();
+
+    let rpn = postfix.to_rpn(String::clone);
+    assert_eq!(
+        rpn,
+        r#"a "b c" "OR" IMPLIES "" "\\\"quoted\\\"" NAND NOT XOR AND"#
+    );
+    assert_eq!(
+        PostfixExpression::parse(&rpn, |s| Some(s.to_string())),
+        Ok(postfix)
+    );
+}
+
+#[test]
+fn test_postfix_rpn_custom_writer() {
+    let postfix = PostfixExpression::parse("1 2 IFF", |s| s.parse::<i32>().ok()).unwrap();
+    let rpn = postfix.to_rpn(|n| format!("#{n}"));
+    assert_eq!(rpn, "#1 #2 IFF");
+    assert_eq!(
+        PostfixExpression::parse(&rpn, |s| s.strip_prefix('#')?.parse::<i32>().ok()),
+        Ok(postfix)
+    );
+}
+
+#[test]
+fn test_postfix_parse_sexpr() {
+    let parse = |input| PostfixExpression::parse_sexpr(input, |s| Some(s.to_string()));
+    for (input, rpn) in [
+        ("a", "a"),
+        ("(and a (or b c))", "a b c OR AND"),
+        (" ( AND a b c ( not d ) ) ", "a b AND c AND d NOT AND"),
+        ("(or (and a b) (and c d) e)", "a b AND c d AND OR e OR"),
+        ("(-> a (-> b c))", "a b c IMPLIES IMPLIES"),
+        (r#"(nor "x > 1" "and")"#, r#""x > 1" "and" NOR"#),
+    ] {
+        assert_eq!(parse(input), parse_rpn(rpn), "{input}");
+    }
+}
+
+fn parse_rpn(input: &str) -> Result<PostfixExpression<String>, ParseError> {
+    PostfixExpression::parse(input, |s| Some(s.to_string()))
+}
+
+#[test]
+fn test_postfix_parse_sexpr_invalid() {
+    for (input, error) in [
+        ("", ParseError::Invalid(ValidationError::EmptyExpression)),
+        ("()", ParseError::UnexpectedToken(1)),
+        ("a b", ParseError::UnexpectedToken(2)),
+        ("(a b)", ParseError::UnexpectedToken(1)),
+        ("(and a)", ParseError::UnexpectedToken(6)),
+        ("(and a b", ParseError::UnexpectedEnd),
+        ("(and", ParseError::UnexpectedEnd),
+        ("(and a b))", ParseError::UnexpectedToken(9)),
+        ("(not a b)", ParseError::UnexpectedToken(7)),
+        ("(implies a b c)", ParseError::UnexpectedToken(13)),
+        ("(and a and)", ParseError::UnexpectedToken(7)),
+        ("((and a b) c)", ParseError::UnexpectedToken(1)),
+        (r#"(or "a"b c)"#, ParseError::UnexpectedCharacter(7)),
+        (r#"(or "a c)"#, ParseError::UnterminatedQuote(4)),
+    ] {
+        assert_eq!(
+            PostfixExpression::parse_sexpr(input, |s| Some(s.to_string())),
+            Err(error),
+            "{input}"
+        );
+    }
+    assert_eq!(
+        PostfixExpression::parse_sexpr("(or 1 (not 300))", |s| s.parse::<u8>().ok()),
+        Err(ParseError::InvalidPredicate(1))
+    );
+}
+
+#[test]
+fn test_postfix_to_sexpr() {
+    for (rpn, sexpr) in [
+        ("a", "a"),
+        ("a b c OR AND", "(and a (or b c))"),
+        ("a b AND c AND d AND", "(and a b c d)"),
+        ("a b AND c d AND AND", "(and a b (and c d))"),
+        ("a b c AND AND", "(and a (and b c))"),
+        ("a b OR c AND d OR", "(or (and (or a b) c) d)"),
+        ("a b IMPLIES c IMPLIES", "(implies (implies a b) c)"),
+        ("a NOT NOT b XOR c XOR", "(xor (not (not a)) b c)"),
+    ] {
+        assert_eq!(parse_rpn(rpn).unwrap().to_sexpr(String::clone), sexpr);
+    }
+}
+
+#[test]
+fn test_postfix_sexpr_round_trip() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("f(x)".to_string()),
+        PostfixToken::Predicate("b c".to_string()),
+        PostfixToken::Predicate("or".to_string()),
+        PostfixToken::Operator(Operator::Implies),
+        PostfixToken::Operator(Operator::Iff),
+        PostfixToken::Predicate(String::new()),
+        PostfixToken::Operator(Operator::Iff),
+        PostfixToken::Predicate(r#"\"quoted\""#.to_string()),
+        PostfixToken::Operator(Operator::Iff),
+    ])
+    .unwrap();
+
+    let sexpr = postfix.to_sexpr(String::clone);
+    assert_eq!(
+        sexpr,
+        r#"(iff "f(x)" (implies "b c" "or") "" "\\\"quoted\\\"")"#
+    );
+    assert_eq!(
+        PostfixExpression::parse_sexpr(&sexpr, |s| Some(s.to_string())),
+        Ok(postfix)
+    );
+}
+
+#[test]
+fn test_postfix_sexpr_round_trip_random() {
+    let mut seed = 0x6a09_e667_f3bc_c908;
+    for _ in 0..500 {
+        let postfix = random_postfix(&mut seed, 6);
+        let sexpr = postfix.to_sexpr(usize::to_string);
+        assert_eq!(
+            PostfixExpression::parse_sexpr(&sexpr, |s| s.parse().ok()).as_ref(),
+            Ok(&postfix),
+            "{sexpr}"
+        );
+    }
+}
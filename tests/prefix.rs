@@ -0,0 +1,291 @@
+use rpn_predicate_interpreter::{
+    InfixExpression, KeywordStyle, Operator, PostfixExpression, PostfixToken, PredicateEvaluator,
+    PrefixExpression, PrefixToken, ValidationError,
+};
+use std::cell::RefCell;
+
+struct Recorder {
+    calls: RefCell<Vec<char>>,
+}
+
+impl PredicateEvaluator for Recorder {
+    type Predicate = (char, bool);
+
+    fn evaluate_predicate(&self, predicate: &Self::Predicate) -> bool {
+        self.calls.borrow_mut().push(predicate.0);
+        predicate.1
+    }
+}
+
+#[test]
+// *a+bc --> abc+*
+fn test_prefix_to_postfix() {
+    let prefix = PrefixExpression::from_tokens(vec![
+        PrefixToken::Operator(Operator::And),
+        PrefixToken::Predicate("a"),
+        PrefixToken::Operator(Operator::Or),
+        PrefixToken::Predicate("b"),
+        PrefixToken::Predicate("c"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        prefix.to_postfix(),
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Predicate("c"),
+            PostfixToken::Operator(Operator::Or),
+            PostfixToken::Operator(Operator::And),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+// ab->!c* --> *!->abc
+fn test_postfix_to_prefix() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Operator(Operator::Implies),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::And),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        postfix.to_prefix(),
+        PrefixExpression::from_tokens(vec![
+            PrefixToken::Operator(Operator::And),
+            PrefixToken::Operator(Operator::Not),
+            PrefixToken::Operator(Operator::Implies),
+            PrefixToken::Predicate("a"),
+            PrefixToken::Predicate("b"),
+            PrefixToken::Predicate("c"),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_prefix_infix_round_trip() {
+    for infix in [
+        "a",
+        "NOT a",
+        "a AND (b OR c)",
+        "(a IMPLIES b) IMPLIES c XOR d",
+        "a NAND (b NOR NOT c) IFF d AND e",
+    ] {
+        let prefix = InfixExpression::parse(infix, Some).unwrap().to_prefix();
+        assert_eq!(prefix.to_infix().to_string(), infix);
+    }
+}
+
+#[test]
+fn test_prefix_evaluate() {
+    for (a, b, c) in [
+        (false, false, false),
+        (false, true, true),
+        (true, false, true),
+        (true, true, false),
+    ] {
+        // -> a xor b !c
+        let prefix = PrefixExpression::from_tokens(vec![
+            PrefixToken::Operator(Operator::Implies),
+            PrefixToken::Predicate(a),
+            PrefixToken::Operator(Operator::Xor),
+            PrefixToken::Predicate(b),
+            PrefixToken::Operator(Operator::Not),
+            PrefixToken::Predicate(c),
+        ])
+        .unwrap();
+        assert_eq!(prefix.evaluate(&()), !a || b == c);
+    }
+}
+
+#[test]
+fn test_prefix_evaluate_short_circuit() {
+    let values = |a, b, c, d| [('a', a), ('b', b), ('c', c), ('d', d)];
+    for (infix, values, result, calls) in [
+        (
+            "a AND (b OR c) AND d",
+            values(false, true, true, true),
+            false,
+            "a",
+        ),
+        (
+            "a AND (b OR c) AND d",
+            values(true, true, true, false),
+            false,
+            "abd",
+        ),
+        (
+            "(a OR b) AND (c NOR d)",
+            values(false, true, true, true),
+            false,
+            "abc",
+        ),
+        (
+            "a IMPLIES NOT (b AND c)",
+            values(true, true, false, true),
+            true,
+            "abc",
+        ),
+        (
+            "a XOR (b OR c) OR d",
+            values(true, true, false, false),
+            false,
+            "abd",
+        ),
+    ] {
+        let prefix = InfixExpression::parse(infix, |s| {
+            values.iter().find(|(n, _)| s.starts_with(*n)).copied()
+        })
+        .unwrap()
+        .to_prefix();
+        let recorder = Recorder {
+            calls: RefCell::new(Vec::new()),
+        };
+        assert_eq!(prefix.evaluate(&recorder), result, "{infix}");
+        assert_eq!(
+            recorder.calls.into_inner().into_iter().collect::<String>(),
+            calls,
+            "{infix}"
+        );
+    }
+}
+
+#[test]
+fn test_prefix_invalid() {
+    let cases = [
+        (vec![], ValidationError::EmptyExpression),
+        (
+            vec![
+                PrefixToken::Operator(Operator::And),
+                PrefixToken::Predicate("a"),
+            ],
+            ValidationError::MissingOperand(2),
+        ),
+        (
+            vec![PrefixToken::Predicate("a"), PrefixToken::Predicate("b")],
+            ValidationError::MissingOperator(1),
+        ),
+        (
+            vec![
+                PrefixToken::Predicate("a"),
+                PrefixToken::Predicate("b"),
+                PrefixToken::Operator(Operator::Or),
+            ],
+            ValidationError::MissingOperator(1),
+        ),
+        (
+            vec![PrefixToken::Operator(Operator::Not)],
+            ValidationError::MissingOperand(1),
+        ),
+    ];
+
+    for (tokens, error) in cases {
+        assert_eq!(PrefixExpression::try_from_tokens(tokens), Err(error));
+    }
+    assert!(PrefixExpression::from_tokens(vec![
+        PrefixToken::Operator(Operator::Not),
+        PrefixToken::Predicate(1)
+    ])
+    .is_some());
+}
+
+#[test]
+fn test_prefix_map() {
+    // ^x!yy
+    let prefix = PrefixExpression::from_tokens(vec![
+        PrefixToken::Operator(Operator::Xor),
+        PrefixToken::Predicate("x"),
+        PrefixToken::Operator(Operator::Not),
+        PrefixToken::Predicate("yy"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        prefix.map_ref(|s| s.len()),
+        PrefixExpression::from_tokens(vec![
+            PrefixToken::Operator(Operator::Xor),
+            PrefixToken::Predicate(1),
+            PrefixToken::Operator(Operator::Not),
+            PrefixToken::Predicate(2),
+        ])
+        .unwrap()
+    );
+
+    let mapped = prefix.map(|s| s == "x");
+    assert!(!mapped.evaluate(&()));
+
+    let result = mapped.try_map(|b| if b { Ok(b) } else { Err(()) });
+    assert_eq!(result.unwrap_err().index(), 1);
+}
+
+#[test]
+fn test_prefix_try_map() {
+    let prefix = InfixExpression::parse("1 AND 2 OR 3", Some)
+        .unwrap()
+        .to_prefix();
+    let parsed = prefix.try_map(|s| s.parse::<u8>()).unwrap();
+    assert_eq!(parsed.predicates().sum::<u8>(), 6);
+    assert_eq!(parsed.to_string(), "1 AND 2 OR 3");
+}
+
+#[test]
+// *a!+bc
+fn test_prefix_tokens_access() {
+    let prefix = PrefixExpression::from_tokens(vec![
+        PrefixToken::Operator(Operator::And),
+        PrefixToken::Predicate("a"),
+        PrefixToken::Operator(Operator::Not),
+        PrefixToken::Operator(Operator::Or),
+        PrefixToken::Predicate("b"),
+        PrefixToken::Predicate("c"),
+    ])
+    .unwrap();
+
+    assert_eq!(prefix.len(), 6);
+    assert!(!prefix.is_empty());
+    assert_eq!(
+        prefix.predicates().copied().collect::<Vec<_>>(),
+        ["a", "b", "c"]
+    );
+
+    let mut operators = Vec::new();
+    for token in &prefix {
+        if let PrefixToken::Operator(op) = token {
+            operators.push(*op);
+        }
+    }
+    assert_eq!(operators, [Operator::And, Operator::Not, Operator::Or]);
+    assert!(prefix.iter().eq(prefix.tokens().iter()));
+    assert!(prefix
+        .into_iter()
+        .any(|token| token == PrefixToken::Predicate("c")));
+}
+
+#[test]
+fn test_prefix_display() {
+    for infix in [
+        "a",
+        "NOT a",
+        "a AND NOT (b OR c)",
+        "(a IMPLIES b) IMPLIES c XOR d",
+        "a NAND (b NOR NOT c) IFF d AND e",
+    ] {
+        let prefix = InfixExpression::parse(infix, Some).unwrap().to_prefix();
+        assert_eq!(prefix.to_string(), infix);
+    }
+
+    let prefix = InfixExpression::parse("a AND NOT (b OR c)", Some)
+        .unwrap()
+        .to_prefix();
+    assert_eq!(
+        prefix.display(KeywordStyle::Symbols).to_string(),
+        "a && ! (b || c)"
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{PostfixExpression, PredicateEvaluator, ScoringEvaluator};
+use std::collections::HashMap;
+
+struct Probabilities(HashMap<String, f64>);
+
+impl ScoringEvaluator for Probabilities {
+    type Predicate = String;
+
+    fn score_predicate(&self, predicate: &String) -> f64 {
+        self.0[predicate]
+    }
+}
+
+struct Assignment(HashMap<String, bool>);
+
+impl PredicateEvaluator for Assignment {
+    type Predicate = String;
+
+    fn evaluate_predicate(&self, predicate: &String) -> bool {
+        self.0[predicate]
+    }
+}
+
+fn probabilities(values: &[(&str, f64)]) -> Probabilities {
+    Probabilities(values.iter().map(|&(p, v)| (p.to_string(), v)).collect())
+}
+
+// sums the probabilities of the assignments making the expression true
+fn brute_force(postfix: &PostfixExpression<String>, probabilities: &Probabilities) -> f64 {
+    let names: Vec<&String> = probabilities.0.keys().collect();
+    let mut total = 0.0;
+    for bits in 0..1u32 << names.len() {
+        let mut weight = 1.0;
+        let mut assignment = HashMap::new();
+        for (i, name) in names.iter().enumerate() {
+            let value = bits & (1 << i) != 0;
+            let p = probabilities.0[*name];
+            weight *= if value { p } else { 1.0 - p };
+            assignment.insert(name.to_string(), value);
+        }
+        if postfix.evaluate(&Assignment(assignment)) {
+            total += weight;
+        }
+    }
+    total
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn test_probability_read_once() {
+    let probabilities = probabilities(&[("A", 0.5), ("B", 0.25), ("C", 0.8)]);
+    for (infix, expected) in [
+        ("A", 0.5),
+        ("NOT A", 0.5),
+        ("A AND B", 0.125),
+        ("A OR B", 0.625),
+        ("A XOR B", 0.5),
+        ("B IMPLIES C", 0.95),
+        ("A AND (B OR C)", 0.425),
+    ] {
+        assert_close(parse(infix).probability(&probabilities), expected);
+    }
+}
+
+#[test]
+fn test_probability_repeated_predicates() {
+    let probabilities = probabilities(&[("A", 0.5), ("B", 0.25), ("C", 0.8)]);
+    for (infix, expected) in [
+        ("A AND A", 0.5),
+        ("A AND NOT A", 0.0),
+        ("A OR NOT A", 1.0),
+        ("A XOR A", 0.0),
+        ("A IFF A", 1.0),
+        ("(A AND B) OR (A AND C)", 0.5 * (1.0 - 0.75 * 0.2)),
+    ] {
+        assert_close(parse(infix).probability(&probabilities), expected);
+    }
+}
+
+#[test]
+fn test_probability_matches_brute_force() {
+    let probabilities = probabilities(&[("A", 0.3), ("B", 0.6), ("C", 0.9), ("D", 0.15)]);
+    for infix in [
+        "(A OR B) AND (A OR C) AND NOT (B AND D)",
+        "A XOR B XOR A XOR C",
+        "(A IMPLIES B) IFF (C NAND A) OR D",
+        "NOT (A NOR B) AND (C OR D) AND (A IMPLIES D) AND (B XOR C)",
+    ] {
+        let postfix = parse(infix);
+        assert_close(
+            postfix.probability(&probabilities),
+            brute_force(&postfix, &probabilities),
+        );
+    }
+}
+
+#[test]
+fn test_probability_certain_predicates() {
+    let probabilities = probabilities(&[("A", 1.0), ("B", 0.0), ("C", 1.5)]);
+    assert_eq!(parse("A AND NOT B").probability(&probabilities), 1.0);
+    assert_eq!(
+        parse("(A OR B) AND (B OR C)").probability(&probabilities),
+        1.0
+    );
+}
+
+#[test]
+fn test_probability_nan() {
+    let probabilities = probabilities(&[("A", f64::NAN), ("B", 0.5)]);
+    assert_eq!(parse("A").probability(&probabilities), 0.0);
+    assert_eq!(parse("A OR B").probability(&probabilities), 0.5);
+    assert_eq!(
+        parse("(A OR B) AND (A OR NOT B)").probability(&probabilities),
+        0.0
+    );
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "serde")]
+
+use rpn_predicate_interpreter::{
+    BitSet, ExpressionTree, InfixExpression, InfixToken, KeywordStyle, Operator, Parenthesis,
+    ParseError, PostfixExpression, PostfixToken, PrefixExpression, PrefixToken, ValidationError,
+};
+
+#[test]
+fn test_serde_tokens() {
+    let tokens = vec![
+        InfixToken::Operator(Operator::Not),
+        InfixToken::Parenthesis(Parenthesis::Open),
+        InfixToken::Predicate("a".to_string()),
+        InfixToken::Operator(Operator::Implies),
+        InfixToken::Predicate("b".to_string()),
+        InfixToken::Parenthesis(Parenthesis::Close),
+    ];
+    let json = serde_json::to_string(&tokens).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"Operator":"Not"},{"Parenthesis":"Open"},{"Predicate":"a"},{"Operator":"Implies"},{"Predicate":"b"},{"Parenthesis":"Close"}]"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Vec<InfixToken<String>>>(&json).unwrap(),
+        tokens
+    );
+}
+
+#[test]
+fn test_serde_infix_expression() {
+    let infix = InfixExpression::parse("a AND (b OR NOT c)", |s| Some(s.to_string())).unwrap();
+    let json = serde_json::to_string(&infix).unwrap();
+    assert_eq!(
+        serde_json::from_str::<InfixExpression<String>>(&json).unwrap(),
+        infix
+    );
+}
+
+#[test]
+fn test_serde_postfix_expression() {
+    let postfix = PostfixExpression::parse("1 2 3 OR AND", |s| s.parse::<u32>().ok()).unwrap();
+    let json = serde_json::to_string(&postfix).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"Predicate":1},{"Predicate":2},{"Predicate":3},{"Operator":"Or"},{"Operator":"And"}]"#
+    );
+    assert_eq!(
+        serde_json::from_str::<PostfixExpression<u32>>(&json).unwrap(),
+        postfix
+    );
+}
+
+#[test]
+fn test_serde_prefix_expression() {
+    let prefix = PrefixExpression::from_tokens(vec![
+        PrefixToken::Operator(Operator::Xor),
+        PrefixToken::Predicate(true),
+        PrefixToken::Predicate(false),
+    ])
+    .unwrap();
+    let json = serde_json::to_string(&prefix).unwrap();
+    assert_eq!(
+        serde_json::from_str::<PrefixExpression<bool>>(&json).unwrap(),
+        prefix
+    );
+}
+
+#[test]
+fn test_serde_invalid_expressions() {
+    let json = r#"[{"Predicate":1},{"Operator":"And"}]"#;
+    let error = serde_json::from_str::<PostfixExpression<u32>>(json).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        ValidationError::MissingOperand(1).to_string()
+    );
+    assert!(serde_json::from_str::<PrefixExpression<u32>>(json).is_err());
+
+    let json = r#"[{"Predicate":1},{"Predicate":2}]"#;
+    assert!(serde_json::from_str::<InfixExpression<u32>>(json).is_err());
+    assert!(serde_json::from_str::<InfixExpression<u32>>("[]").is_err());
+}
+
+#[test]
+fn test_serde_tree_and_others() {
+    let tree = ExpressionTree::And(
+        Box::new(ExpressionTree::Leaf(1)),
+        Box::new(ExpressionTree::Not(Box::new(ExpressionTree::Leaf(2)))),
+    );
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(json, r#"{"And":[{"Leaf":1},{"Not":{"Leaf":2}}]}"#);
+    assert_eq!(
+        serde_json::from_str::<ExpressionTree<i32>>(&json).unwrap(),
+        tree
+    );
+
+    let json =
+        serde_json::to_string(&(KeywordStyle::Logic, ValidationError::AdjacentPredicates(3)))
+            .unwrap();
+    assert_eq!(json, r#"["Logic",{"AdjacentPredicates":3}]"#);
+    assert_eq!(
+        serde_json::from_str::<PostfixToken<u8>>(r#"{"Operator":"Nand"}"#).unwrap(),
+        PostfixToken::Operator(Operator::Nand)
+    );
+
+    let error = ParseError::Invalid(ValidationError::MissingOperator(2));
+    let json = serde_json::to_string(&error).unwrap();
+    assert_eq!(json, r#"{"Invalid":{"MissingOperator":2}}"#);
+    assert_eq!(serde_json::from_str::<ParseError>(&json).unwrap(), error);
+}
+
+#[test]
+fn test_serde_bit_set() {
+    let bits: BitSet = [true, false, true].into_iter().collect();
+    let json = serde_json::to_string(&bits).unwrap();
+    assert_eq!(json, r#"{"words":[5],"len":3}"#);
+    assert_eq!(serde_json::from_str::<BitSet>(&json).unwrap(), bits);
+
+    for json in [
+        r#"{"words":[],"len":10}"#,
+        r#"{"words":[0,0],"len":64}"#,
+        r#"{"words":[18446744073709551615],"len":3}"#,
+    ] {
+        assert!(serde_json::from_str::<BitSet>(json).is_err(), "{json}");
+    }
+}
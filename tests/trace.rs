@@ -0,0 +1,109 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{KeywordStyle, Operator, PredicateEvaluator, TraceStep};
+
+/// Evaluates predicates named by a single letter, true for the given letters.
+struct Letters(&'static str);
+
+impl PredicateEvaluator for Letters {
+    type Predicate = String;
+
+    fn evaluate_predicate(&self, predicate: &String) -> bool {
+        self.0.contains(predicate.as_str())
+    }
+}
+
+#[test]
+fn test_trace_steps() {
+    // a b c OR AND
+    let postfix = parse("A AND (B OR C)");
+    let (result, trace) = postfix.evaluate_traced(&Letters("AC"));
+    assert!(result);
+    assert_eq!(
+        trace.steps(),
+        &[
+            TraceStep::Predicate {
+                index: 0,
+                value: true
+            },
+            TraceStep::Predicate {
+                index: 1,
+                value: false
+            },
+            TraceStep::Predicate {
+                index: 2,
+                value: true
+            },
+            TraceStep::Operator {
+                index: 3,
+                operator: Operator::Or,
+                operands: vec![false, true],
+                value: true
+            },
+            TraceStep::Operator {
+                index: 4,
+                operator: Operator::And,
+                operands: vec![true, true],
+                value: true
+            },
+        ]
+    );
+    assert_eq!(trace.to_string(), "A[true] AND (B[false] OR C[true])");
+}
+
+#[test]
+fn test_trace_short_circuit() {
+    let postfix = parse("A AND (B OR C)");
+    let (result, trace) = postfix.evaluate_traced(&Letters("BC"));
+    assert!(!result);
+    assert_eq!(
+        trace.steps(),
+        &[
+            TraceStep::Predicate {
+                index: 0,
+                value: false
+            },
+            TraceStep::Operator {
+                index: 4,
+                operator: Operator::And,
+                operands: vec![false],
+                value: false
+            },
+        ]
+    );
+    assert_eq!(trace.to_string(), "A[false] AND (B OR C)");
+}
+
+#[test]
+fn test_trace_matches_evaluate() {
+    for infix in [
+        "A",
+        "NOT A OR B",
+        "(A OR B) AND (C OR D)",
+        "A IMPLIES B IMPLIES C",
+        "A XOR B NAND NOT (C NOR D) IFF A",
+    ] {
+        let postfix = parse(infix);
+        for letters in ["", "A", "B", "AC", "BD", "ABCD"] {
+            let (result, trace) = postfix.evaluate_traced(&Letters(letters));
+            assert_eq!(result, postfix.evaluate(&Letters(letters)), "{infix}");
+            // the last step is the evaluation of the whole expression
+            let last = trace.steps().last().unwrap();
+            assert_eq!(last.value(), result, "{infix}");
+            assert_eq!(last.index(), postfix.len() - 1, "{infix}");
+        }
+    }
+}
+
+#[test]
+fn test_trace_display() {
+    let postfix = parse("NOT A OR (B IMPLIES C) AND D");
+    let (_, trace) = postfix.evaluate_traced(&Letters("BD"));
+    assert_eq!(trace.to_string(), "NOT A[false] OR (B IMPLIES C) AND D");
+    let (_, trace) = postfix.evaluate_traced(&Letters("ABCD"));
+    assert_eq!(
+        trace.display(KeywordStyle::Symbols).to_string(),
+        "! A[true] || (B[true] -> C[true]) && D[true]"
+    );
+}
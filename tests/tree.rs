@@ -0,0 +1,127 @@
+use rpn_predicate_interpreter::{
+    ExpressionTree, InfixExpression, InfixToken, Operator, Parenthesis, PostfixExpression,
+    PostfixToken, ValidationError,
+};
+
+fn leaf(p: &str) -> Box<ExpressionTree<&str>> {
+    Box::new(ExpressionTree::Leaf(p))
+}
+
+#[test]
+// abc+!* --> a*!(b+c)
+fn test_tree_from_postfix() {
+    let postfix = PostfixExpression::from_tokens(vec![
+        PostfixToken::Predicate("a"),
+        PostfixToken::Predicate("b"),
+        PostfixToken::Predicate("c"),
+        PostfixToken::Operator(Operator::Or),
+        PostfixToken::Operator(Operator::Not),
+        PostfixToken::Operator(Operator::And),
+    ])
+    .unwrap();
+
+    let tree = ExpressionTree::from(postfix);
+    assert_eq!(
+        tree,
+        ExpressionTree::And(
+            leaf("a"),
+            Box::new(ExpressionTree::Not(Box::new(ExpressionTree::Or(
+                leaf("b"),
+                leaf("c")
+            ))))
+        )
+    );
+    assert_eq!(tree.operator(), Some(Operator::And));
+}
+
+#[test]
+// a->b->c --> a->(b->c)
+fn test_tree_from_infix() {
+    let infix = InfixExpression::parse("a IMPLIES b IMPLIES c XOR d", Some).unwrap();
+
+    assert_eq!(
+        infix.to_tree(),
+        ExpressionTree::Implies(
+            leaf("a"),
+            Box::new(ExpressionTree::Implies(
+                leaf("b"),
+                Box::new(ExpressionTree::Xor(leaf("c"), leaf("d")))
+            ))
+        )
+    );
+}
+
+#[test]
+fn test_tree_to_postfix_and_infix() {
+    let tree = || {
+        ExpressionTree::Nand(
+            Box::new(ExpressionTree::Iff(leaf("a"), leaf("b"))),
+            Box::new(ExpressionTree::Nor(leaf("c"), leaf("d"))),
+        )
+    };
+
+    assert_eq!(
+        tree().to_postfix(),
+        PostfixExpression::from_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Predicate("b"),
+            PostfixToken::Operator(Operator::Iff),
+            PostfixToken::Predicate("c"),
+            PostfixToken::Predicate("d"),
+            PostfixToken::Operator(Operator::Nor),
+            PostfixToken::Operator(Operator::Nand),
+        ])
+        .unwrap()
+    );
+    assert_eq!(
+        InfixExpression::from(tree()),
+        InfixExpression::parse("(a IFF b) NAND (c NOR d)", Some).unwrap()
+    );
+}
+
+#[test]
+fn test_tree_round_trip() {
+    for infix in [
+        "a",
+        "NOT NOT a",
+        "a AND (b OR c) AND NOT d",
+        "a NAND (b NAND c) NOR d IFF e XOR f IMPLIES g",
+        "(a IMPLIES b) IMPLIES c",
+    ] {
+        let postfix = || InfixExpression::parse(infix, Some).unwrap().to_postfix();
+        let tree = ExpressionTree::from(postfix());
+        assert_eq!(PostfixExpression::from(tree), postfix());
+
+        let tree = ExpressionTree::from(InfixExpression::parse(infix, Some).unwrap());
+        assert_eq!(tree.to_infix().to_string(), infix);
+    }
+}
+
+#[test]
+fn test_tree_try_from_tokens() {
+    assert_eq!(
+        ExpressionTree::try_from_postfix_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Operator(Operator::Not),
+        ]),
+        Ok(ExpressionTree::Not(leaf("a")))
+    );
+    assert_eq!(
+        ExpressionTree::try_from_postfix_tokens(vec![
+            PostfixToken::Predicate("a"),
+            PostfixToken::Operator(Operator::Or),
+        ]),
+        Err(ValidationError::MissingOperand(1))
+    );
+    assert_eq!(
+        ExpressionTree::try_from_infix_tokens(vec![
+            InfixToken::Parenthesis(Parenthesis::Open),
+            InfixToken::Predicate("a"),
+        ]),
+        Err(ValidationError::UnbalancedParenthesis(0))
+    );
+    assert_eq!(
+        ExpressionTree::<&str>::try_from_infix_tokens(vec![]),
+        Err(ValidationError::EmptyExpression)
+    );
+}
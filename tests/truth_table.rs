@@ -0,0 +1,89 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{TableStyle, TruthTable};
+
+#[test]
+fn test_truth_table() {
+    let postfix = parse::<String>("A AND (B OR A)");
+    let table = postfix.truth_table().unwrap();
+    assert_eq!(table.predicates(), [&"A".to_string(), &"B".to_string()]);
+    let rows: Vec<(Vec<bool>, bool)> = table.rows().collect();
+    assert_eq!(
+        rows,
+        [
+            (vec![false, false], false),
+            (vec![false, true], false),
+            (vec![true, false], true),
+            (vec![true, true], true),
+        ]
+    );
+    assert_eq!(table.results().count_ones(), 2);
+    assert_eq!(table.assignment(2), [true, false]);
+}
+
+#[test]
+fn test_truth_table_too_many_predicates() {
+    let infix = |count: usize| {
+        (0..count)
+            .map(|i| format!("P{i}"))
+            .collect::<Vec<_>>()
+            .join(" OR ")
+    };
+    let postfix = parse::<String>(&infix(TruthTable::<String>::MAX_PREDICATES));
+    let table = postfix.truth_table().unwrap();
+    assert_eq!(
+        table.results().len(),
+        1 << TruthTable::<String>::MAX_PREDICATES
+    );
+    assert_eq!(table.results().count_ones(), table.results().len() - 1);
+    assert!(
+        parse::<String>(&infix(TruthTable::<String>::MAX_PREDICATES + 1))
+            .truth_table()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_truth_table_display() {
+    let postfix = parse::<String>("Rain IMPLIES Wet");
+    let table = postfix.truth_table().unwrap();
+    assert_eq!(
+        table.to_string(),
+        "\
+| Rain | Wet | Result |
+|------|-----|--------|
+| F    | F   | T      |
+| F    | T   | T      |
+| T    | F   | F      |
+| T    | T   | T      |
+"
+    );
+    assert_eq!(
+        table.display(TableStyle::Ascii).to_string(),
+        "\
++------+-----+--------+
+| Rain | Wet | Result |
++------+-----+--------+
+| F    | F   | T      |
+| F    | T   | T      |
+| T    | F   | F      |
+| T    | T   | T      |
++------+-----+--------+
+"
+    );
+}
+
+#[test]
+fn test_truth_table_display_escapes_pipes() {
+    let postfix = parse::<String>("a|b OR c");
+    let table = postfix.truth_table().unwrap();
+    assert_eq!(
+        table.to_string().lines().take(2).collect::<Vec<_>>(),
+        ["| a\\|b | c | Result |", "|------|---|--------|"]
+    );
+    assert_eq!(
+        table.display(TableStyle::Ascii).to_string().lines().nth(1),
+        Some("| a|b | c | Result |")
+    );
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use common::parse;
+use rpn_predicate_interpreter::{PostfixExpression, PredicateEvaluator};
+
+/// Assigns values to the predicates `0` to `5` from the bits of a number.
+struct Assignment(u32);
+
+impl PredicateEvaluator for Assignment {
+    type Predicate = u32;
+
+    fn evaluate_predicate(&self, predicate: &u32) -> bool {
+        self.0 & (1 << predicate) != 0
+    }
+}
+
+/// Whether fixing the given predicates always gives `result`, whatever the other ones.
+fn is_sufficient(
+    postfix: &PostfixExpression<u32>,
+    witness: &[(usize, bool)],
+    result: bool,
+) -> bool {
+    let predicates: Vec<u32> = postfix.predicates().copied().collect();
+    (0..64).all(|bits| {
+        let mut bits = bits;
+        for &(index, value) in witness {
+            let bit = 1 << predicates[index];
+            bits = if value { bits | bit } else { bits & !bit };
+        }
+        postfix.evaluate(&Assignment(bits)) == result
+    })
+}
+
+#[test]
+fn test_witness_examples() {
+    let postfix = parse("0 AND (1 OR 2)");
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b110)),
+        (false, vec![(0, false)])
+    );
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b101)),
+        (true, vec![(0, true), (2, true)])
+    );
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b001)),
+        (false, vec![(1, false), (2, false)])
+    );
+
+    // the true consequent alone justifies the implication
+    let postfix = parse("0 IMPLIES 1");
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b11)),
+        (true, vec![(1, true)])
+    );
+
+    // the false first operand doesn't matter
+    let postfix = parse("(0 AND 1) OR 2");
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b110)),
+        (true, vec![(2, true)])
+    );
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b111)),
+        (true, vec![(0, true), (1, true)])
+    );
+
+    let postfix = parse("NOT (0 XOR 1)");
+    assert_eq!(
+        postfix.evaluate_witness(&Assignment(0b01)),
+        (false, vec![(0, true), (1, false)])
+    );
+}
+
+#[test]
+fn test_witness_sufficient_and_minimal() {
+    for infix in [
+        "0",
+        "0 AND 1 AND 2",
+        "0 OR 1 AND 2 OR NOT 3",
+        "(0 OR 1) AND (2 OR 3) AND NOT (4 AND 5)",
+        "0 NAND (1 NOR 2) NAND 3",
+        "0 IMPLIES 1 IMPLIES 2 AND 3",
+        "(0 XOR 1) IFF (2 OR 3 AND 4)",
+    ] {
+        let postfix = parse(infix);
+        for bits in 0..64 {
+            let (result, witness) = postfix.evaluate_witness(&Assignment(bits));
+            assert_eq!(result, postfix.evaluate(&Assignment(bits)), "{infix}");
+            assert!(
+                is_sufficient(&postfix, &witness, result),
+                "{infix} {bits:06b}"
+            );
+            for i in 0..witness.len() {
+                let mut smaller = witness.clone();
+                smaller.remove(i);
+                assert!(
+                    !is_sufficient(&postfix, &smaller, result),
+                    "{infix} {bits:06b}"
+                );
+            }
+        }
+    }
+}